@@ -99,7 +99,7 @@ const _: () = {
         fn as_mnn_tensor(&self) -> Option<mnn::Tensor<mnn::Ref<mnn::Host<Self::H>>>> {
             let shape = self.shape().iter().map(|i| *i as i32).collect::<Vec<_>>();
             let data = self.as_slice()?;
-            Some(mnn::Tensor::borrowed(shape, data))
+            mnn::Tensor::try_borrowed(shape, data).ok()
         }
     }
 
@@ -113,7 +113,7 @@ const _: () = {
         fn as_mnn_tensor_mut(&mut self) -> Option<mnn::Tensor<mnn::RefMut<mnn::Host<Self::H>>>> {
             let shape = self.shape().iter().map(|i| *i as i32).collect::<Vec<_>>();
             let data = self.as_slice_mut()?;
-            Some(mnn::Tensor::borrowed_mut(shape, data))
+            mnn::Tensor::try_borrowed_mut(shape, data).ok()
         }
     }
 };
@@ -147,3 +147,18 @@ pub fn test_ndarray_to_tensor_ref() {
     let t = arr.as_mnn_tensor().unwrap();
     assert_eq!(t.host(), &[64; 6]);
 }
+#[cfg(feature = "half")]
+#[test]
+pub fn test_half_tensor_to_ndarray() {
+    use mnn::half::f16;
+    let mut tensor: mnn::Tensor<mnn::Host<f16>> =
+        mnn::Tensor::new([1, 2, 3], mnn::DimensionType::Caffe);
+    tensor.fill(f16::ONE);
+    let ndarr = tensor.as_ndarray::<Ix3>();
+    assert_eq!(ndarr, ndarray::Array3::from_elem([1, 2, 3], f16::ONE));
+    let arr = ndarray::Array2::from_elem([2, 2], f16::from_f32(0.25));
+    assert_eq!(
+        arr.as_mnn_tensor().unwrap().host(),
+        &[f16::from_f32(0.25); 4]
+    );
+}
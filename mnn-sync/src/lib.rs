@@ -197,6 +197,9 @@ impl SessionState {
     }
 }
 
+/// An interpreter together with a session created from it
+///
+/// The session shares the net with the interpreter, so the two can be dropped in any order.
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct SessionRunner {
@@ -205,7 +208,13 @@ pub struct SessionRunner {
 }
 
 impl SessionRunner {
+    /// # Panics
+    /// If `session` wasn't created from `interpreter`
     pub fn new(interpreter: Interpreter, session: Session) -> Self {
+        assert!(
+            interpreter.owns(&session),
+            "The session was created from another interpreter"
+        );
         Self {
             interpreter,
             session,
@@ -227,11 +236,16 @@ impl SessionRunner {
         })
     }
 
+    /// Release the session and return the interpreter
+    ///
+    /// The net itself is kept, only the memory of the session is freed.
     pub fn unload(self) -> Result<mnn::Interpreter> {
-        let session = self.session;
-        let net = self.interpreter;
+        let Self {
+            interpreter,
+            session,
+        } = self;
         drop(session);
-        Ok(net)
+        Ok(interpreter)
     }
 
     pub fn run_session(&mut self) -> Result<()> {
@@ -654,3 +668,18 @@ pub fn test_load_unload() {
         .expect("Failed to run");
     session_handle.unload().expect("Failed to unload");
 }
+
+#[test]
+#[should_panic(expected = "another interpreter")]
+pub fn test_session_runner_rejects_foreign_session() {
+    let mut first = Interpreter::from_file("../tests/assets/realesr.mnn")
+        .expect("Failed to create interpreter");
+    let second = Interpreter::from_file("../tests/assets/realesr.mnn")
+        .expect("Failed to create interpreter");
+    let session = first
+        .create_session(ScheduleConfig::new())
+        .expect("Failed to create session");
+    // The session keeps the first net alive on its own
+    drop(first);
+    SessionRunner::new(second, session);
+}
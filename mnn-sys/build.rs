@@ -8,6 +8,8 @@ use std::{
 };
 const VENDOR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/vendor");
 const MANIFEST_DIR: &str = env!("CARGO_MANIFEST_DIR");
+/// The shim which is allowed to include the private headers in `vendor/source`
+const INTERNAL_SHIM: &str = "internal_c.cpp";
 static TARGET_OS: LazyLock<String> =
     LazyLock::new(|| std::env::var("CARGO_CFG_TARGET_OS").expect("CARGO_CFG_TARGET_OS not set"));
 static TARGET_ARCH: LazyLock<String> = LazyLock::new(|| {
@@ -215,6 +217,7 @@ pub fn mnn_c_bindgen(vendor: impl AsRef<Path>, out: impl AsRef<Path>) -> Result<
     const HEADERS: &[&str] = &[
         "error_code_c.h",
         "interpreter_c.h",
+        "model_info_c.h",
         "tensor_c.h",
         "backend_c.h",
         "schedule_c.h",
@@ -293,7 +296,9 @@ pub fn mnn_cpp_bindgen(vendor: impl AsRef<Path>, out: impl AsRef<Path>) -> Resul
                 .join("Interpreter.hpp")
                 .to_string_lossy(),
         )
-        .allowlist_item(".*SessionInfoCode.*");
+        .allowlist_item(".*SessionInfoCode.*")
+        .allowlist_item(".*HintMode.*")
+        .allowlist_item(".*GeometryComputeMask.*");
     // let cmd = bindings.command_line_flags().join(" ");
     // println!("cargo:warn=bindgen: {}", cmd);
     let bindings = bindings.generate()?;
@@ -303,13 +308,45 @@ pub fn mnn_cpp_bindgen(vendor: impl AsRef<Path>, out: impl AsRef<Path>) -> Resul
 
 pub fn mnn_c_build(path: impl AsRef<Path>, vendor: impl AsRef<Path>) -> Result<()> {
     let mnn_c = path.as_ref();
+    // internal_c.cpp is the only shim which uses the private headers of MNN, it's compiled on
+    // its own so the other shims can't include them by accident
+    let internal = mnn_c.join(INTERNAL_SHIM);
     let files = mnn_c.read_dir()?.flatten().map(|e| e.path()).filter(|e| {
-        e.extension() == Some(std::ffi::OsStr::new("cpp"))
-            || e.extension() == Some(std::ffi::OsStr::new("c"))
+        (e.extension() == Some(std::ffi::OsStr::new("cpp"))
+            || e.extension() == Some(std::ffi::OsStr::new("c")))
+            && *e != internal
     });
     let vendor = vendor.as_ref();
-    cc::Build::new()
+    mnn_c_config(vendor)
+        .files(files)
+        // .pipe(|build| {
+        //     let c = build.get_compiler();
+        //     use std::io::Write;
+        //     writeln!(
+        //         std::fs::File::create("./command.txt").unwrap(),
+        //         "{:?}",
+        //         c.to_command()
+        //     )
+        //     .unwrap();
+        //     build
+        // })
+        .try_compile("mnn_c")
+        .context("Failed to compile mnn_c library")?;
+    mnn_c_config(vendor)
+        .include(vendor.join("source"))
+        .file(internal)
+        .try_compile("mnn_c_internal")
+        .context("Failed to compile mnn_c_internal library")?;
+    Ok(())
+}
+
+/// The compiler configuration shared by all the shims
+fn mnn_c_config(vendor: &Path) -> cc::Build {
+    let mut build = cc::Build::new();
+    build
         .include(vendor.join("include"))
+        .include(vendor.join("schema").join("current"))
+        .include(vendor.join("3rd_party").join("flatbuffers").join("include"))
         // .includes(vulkan_includes(vendor))
         .pipe(|config| {
             #[cfg(feature = "vulkan")]
@@ -333,22 +370,8 @@ pub fn mnn_c_build(path: impl AsRef<Path>, vendor: impl AsRef<Path>) -> Result<(
             config
         })
         .cpp(true)
-        .files(files)
-        .std("c++14")
-        // .pipe(|build| {
-        //     let c = build.get_compiler();
-        //     use std::io::Write;
-        //     writeln!(
-        //         std::fs::File::create("./command.txt").unwrap(),
-        //         "{:?}",
-        //         c.to_command()
-        //     )
-        //     .unwrap();
-        //     build
-        // })
-        .try_compile("mnn_c")
-        .context("Failed to compile mnn_c library")?;
-    Ok(())
+        .std("c++14");
+    build
 }
 
 pub fn build_cmake(path: impl AsRef<Path>, install: impl AsRef<Path>) -> Result<()> {
@@ -0,0 +1,69 @@
+// The only shim which uses the private headers of MNN from vendor/source, it's
+// compiled on its own by build.rs. Keep everything which needs the internals of
+// MNN in here and the rest of the shims on the public headers.
+#include "interpreter_c.h"
+#include "tensor_c.h"
+#include "backend/cpu/CPUTensorConvert.hpp"
+#include "core/Backend.hpp"
+#include "core/TensorUtils.hpp"
+#include <memory>
+static_assert(TENSOR_SHAPE_MAX_DIMS >= MNN_MAX_TENSOR_DIM,
+              "TensorShape can't hold every shape MNN can create");
+extern "C" {
+int Backend_getType(const Backend *backend) {
+  if (backend == nullptr) {
+    return -1;
+  }
+  return static_cast<int>(
+      reinterpret_cast<const MNN::Backend *>(backend)->type());
+}
+int Tensor_getBackendType(const Tensor *tensor) {
+  auto mnn_tensor = reinterpret_cast<const MNN::Tensor *>(tensor);
+  auto backend = MNN::TensorUtils::getDescribeOrigin(mnn_tensor)->getBackend();
+  if (backend == nullptr) {
+    return -1;
+  }
+  return static_cast<int>(backend->type());
+}
+DimensionType Tensor_getDataFormat(const Tensor *tensor) {
+  // getDimensionType reports NC4HW4 tensors as CAFFE
+  auto mnn_tensor = reinterpret_cast<const MNN::Tensor *>(tensor);
+  switch (MNN::TensorUtils::getDescribe(mnn_tensor)->dimensionFormat) {
+  case MNN::MNN_DATA_FORMAT_NHWC:
+    return TENSORFLOW;
+  case MNN::MNN_DATA_FORMAT_NC4HW4:
+    return CAFFE_C4;
+  default:
+    return CAFFE;
+  }
+}
+// Alias the memory of tensor as unsigned integers of the same size in its own
+// layout
+static MNN::Tensor *aliasAsUint(const MNN::Tensor *tensor) {
+  auto type = halide_type_t(halide_type_uint, tensor->getType().bytes() * 8);
+  auto alias = MNN::Tensor::create(tensor->shape(), type,
+                                   tensor->host<void>(), MNN::Tensor::CAFFE);
+  MNN::TensorUtils::getDescribe(alias)->dimensionFormat =
+      MNN::TensorUtils::getDescribe(tensor)->dimensionFormat;
+  return alias;
+}
+int Tensor_convertLayout(const Tensor *src, Tensor *dst) {
+  auto input = reinterpret_cast<const MNN::Tensor *>(src);
+  auto output = reinterpret_cast<const MNN::Tensor *>(dst);
+  auto bytes = input->getType().bytes();
+  if (input->host<void>() == nullptr || output->host<void>() == nullptr ||
+      input->getType() != output->getType()) {
+    return 0;
+  }
+  // MNN has no 64 bit tensors, it loads them as 32 bit
+  if (bytes != 1 && bytes != 2 && bytes != 4) {
+    return 0;
+  }
+  // The converter takes the size of floats from the CPU functions, which may
+  // be 2 bytes on low precision, so the bits are converted as integers
+  std::unique_ptr<MNN::Tensor> in(aliasAsUint(input));
+  std::unique_ptr<MNN::Tensor> out(aliasAsUint(output));
+  return MNN::CPUTensorConverter::convert(in.get(), out.get()) ==
+         MNN::NO_ERROR;
+}
+} // extern "C"
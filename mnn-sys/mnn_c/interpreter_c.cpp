@@ -1,14 +1,24 @@
 #include "interpreter_c.h"
 #include "MNN/Interpreter.hpp"
 #include <MNN/MNNForwardType.h>
+#include <algorithm>
 #include <cstdlib>
 #include <cstring>
 #include <iostream>
+#include <vector>
+
+// MNNForwardType has no count, leave room for the types added by newer MNN
+// versions
+static constexpr size_t kMaxForwardTypes = 64;
+static_assert(MNN_FORWARD_ALL < kMaxForwardTypes,
+              "kMaxForwardTypes must cover every MNNForwardType");
+
 extern "C" {
 // int rust_closure_callback_runner(void *closure, Tensor *const *tensors,
 //                                  size_t tensorCount, const char *opName);
 int rust_closure_callback_runner_op(void *closure, Tensor *const *tensors,
-                                    size_t tensorCount, const void *op);
+                                    size_t tensorCount, const void *op,
+                                    int forwardType);
 
 void modelPrintIO(const char *model) {
   auto net = MNN::Interpreter::createFromFile(model);
@@ -28,7 +38,7 @@ void modelPrintIO(const char *model) {
   }
 }
 
-// const char *getVersion() { return MNN::getVersion(); }
+const char *getVersion() { return MNN::getVersion(); }
 Interpreter *Interpreter_createFromFile(const char *file) {
   return reinterpret_cast<Interpreter *>(
       MNN::Interpreter::createFromFile(file));
@@ -67,28 +77,67 @@ void Interpreter_setSessionHint(Interpreter *interpreter, int mode, int value) {
   mnn_interpreter->setSessionHint(static_cast<MNN::Interpreter::HintMode>(mode),
                                   value);
 }
-// RuntimeInfo* Interpreter_createRuntime(const ScheduleConfig* configs, size_t
-// configSize) {
-//     std::vector<MNN::ScheduleConfig> cppConfigs(configSize);
-//     for (size_t i = 0; i < configSize; ++i) {
-//         cppConfigs[i].saveTensors.assign(configs[i].saveTensors,
-//         configs[i].saveTensors + configs[i].saveTensorsSize);
-//         cppConfigs[i].type = configs[i].type;
-//         cppConfigs[i].numThread = configs[i].numThread;
-//         cppConfigs[i].path.inputs.assign(configs[i].path.inputs,
-//         configs[i].path.inputs + configs[i].path.inputsSize);
-//         cppConfigs[i].path.outputs.assign(configs[i].path.outputs,
-//         configs[i].path.outputs + configs[i].path.outputsSize);
-//         cppConfigs[i].path.mode =
-//         static_cast<MNN::ScheduleConfig::Path::Mode>(configs[i].path.mode);
-//         cppConfigs[i].backupType = configs[i].backupType;
-//         cppConfigs[i].backendConfig = configs[i].backendConfig;
-//     }
-//     auto runtimeInfo = MNN::Interpreter::createRuntime(cppConfigs);
-//     return new RuntimeInfo{new std::map<MNNForwardType,
-//     std::shared_ptr<MNN::Runtime>>(runtimeInfo.first), new
-//     std::shared_ptr<MNN::Runtime>(runtimeInfo.second)};
-// }
+void Interpreter_setSessionHintArray(Interpreter *interpreter, int mode,
+                                     const int *value, size_t size) {
+  auto mnn_interpreter = reinterpret_cast<MNN::Interpreter *>(interpreter);
+  mnn_interpreter->setSessionHint(static_cast<MNN::Interpreter::HintMode>(mode),
+                                  const_cast<int *>(value), size);
+}
+static std::vector<MNN::ScheduleConfig>
+toScheduleConfigs(const MNNScheduleConfig *const *configs, size_t configSize) {
+  auto mnn_configs =
+      reinterpret_cast<const MNN::ScheduleConfig *const *>(configs);
+  std::vector<MNN::ScheduleConfig> s_configs;
+  for (size_t i = 0; i < configSize; ++i) {
+    s_configs.push_back(*mnn_configs[i]);
+  }
+  return s_configs;
+}
+RuntimeInfo *Interpreter_createRuntime(const MNNScheduleConfig *const *configs,
+                                       size_t configSize) {
+  auto runtime = new MNN::RuntimeInfo(
+      MNN::Interpreter::createRuntime(toScheduleConfigs(configs, configSize)));
+  if (runtime->first.empty()) {
+    delete runtime;
+    return nullptr;
+  }
+  return reinterpret_cast<RuntimeInfo *>(runtime);
+}
+void RuntimeInfo_destroy(RuntimeInfo *runtime) {
+  delete reinterpret_cast<MNN::RuntimeInfo *>(runtime);
+}
+static MNN::Runtime *RuntimeInfo_find(const RuntimeInfo *runtime,
+                                      int forwardType) {
+  auto mnn_runtime = reinterpret_cast<const MNN::RuntimeInfo *>(runtime);
+  auto iter =
+      mnn_runtime->first.find(static_cast<MNNForwardType>(forwardType));
+  if (iter == mnn_runtime->first.end()) {
+    return nullptr;
+  }
+  return iter->second.get();
+}
+int RuntimeInfo_hasBackend(const RuntimeInfo *runtime, int forwardType) {
+  return RuntimeInfo_find(runtime, forwardType) != nullptr;
+}
+int RuntimeInfo_setCache(RuntimeInfo *runtime, int forwardType,
+                         const void *buffer, size_t size) {
+  auto backend = RuntimeInfo_find(runtime, forwardType);
+  if (backend == nullptr) {
+    return 0;
+  }
+  return backend->onSetCache(buffer, size);
+}
+const void *RuntimeInfo_getCache(RuntimeInfo *runtime, int forwardType,
+                                 size_t *size) {
+  *size = 0;
+  auto backend = RuntimeInfo_find(runtime, forwardType);
+  if (backend == nullptr) {
+    return nullptr;
+  }
+  auto cache = backend->onGetCache();
+  *size = cache.second;
+  return cache.first;
+}
 Session *Interpreter_createSession(Interpreter *interpreter,
                                    const MNNScheduleConfig *config) {
   auto mnn_interpreter = reinterpret_cast<MNN::Interpreter *>(interpreter);
@@ -98,21 +147,16 @@ Session *Interpreter_createSession(Interpreter *interpreter,
   return reinterpret_cast<Session *>(
       mnn_interpreter->createSession(*mnn_schedule_config));
 }
-// Session* Interpreter_createSessionWithRuntime(Interpreter* interpreter, const
-// ScheduleConfig* config, const RuntimeInfo* runtime) {
-//     MNN::ScheduleConfig cppConfig;
-//     cppConfig.saveTensors.assign(config->saveTensors, config->saveTensors +
-//     config->saveTensorsSize); cppConfig.type = config->type;
-//     cppConfig.numThread = config->numThread;
-//     cppConfig.path.inputs.assign(config->path.inputs, config->path.inputs +
-//     config->path.inputsSize);
-//     cppConfig.path.outputs.assign(config->path.outputs, config->path.outputs
-//     + config->path.outputsSize); cppConfig.path.mode =
-//     static_cast<MNN::ScheduleConfig::Path::Mode>(config->path.mode);
-//     cppConfig.backupType = config->backupType;
-//     cppConfig.backendConfig = config->backendConfig;
-//     return interpreter->createSession(cppConfig, *runtime);
-// }
+Session *Interpreter_createSessionWithRuntime(Interpreter *interpreter,
+                                              const MNNScheduleConfig *config,
+                                              const RuntimeInfo *runtime) {
+  auto mnn_interpreter = reinterpret_cast<MNN::Interpreter *>(interpreter);
+  auto mnn_schedule_config =
+      reinterpret_cast<const MNN::ScheduleConfig *>(config);
+  auto mnn_runtime = reinterpret_cast<const MNN::RuntimeInfo *>(runtime);
+  return reinterpret_cast<Session *>(
+      mnn_interpreter->createSession(*mnn_schedule_config, *mnn_runtime));
+}
 // Session *Interpreter_createMultiPathSession(Interpreter *interpreter,
 //                                             const MNNScheduleConfig *configs,
 //                                             size_t configSize) {
@@ -144,6 +188,15 @@ Interpreter_createMultiPathSession(Interpreter *interpreter,
   MNN::Session *session = mnn_interpreter->createMultiPathSession(s_configs);
   return reinterpret_cast<Session *>(session);
 }
+Session *Interpreter_createMultiPathSessionWithRuntime(
+    Interpreter *interpreter, const MNNScheduleConfig *const *configs,
+    size_t configSize, const RuntimeInfo *runtime) {
+  auto mnn_interpreter = reinterpret_cast<MNN::Interpreter *>(interpreter);
+  auto mnn_runtime = reinterpret_cast<const MNN::RuntimeInfo *>(runtime);
+  MNN::Session *session = mnn_interpreter->createMultiPathSession(
+      toScheduleConfigs(configs, configSize), *mnn_runtime);
+  return reinterpret_cast<Session *>(session);
+}
 
 // Session* Interpreter_createMultiPathSessionWithRuntime(Interpreter*
 // interpreter, const ScheduleConfig* configs, size_t configSize, const
@@ -229,28 +282,39 @@ ErrorCode Interpreter_runSessionWithCallBackInfo(const Interpreter *interpreter,
                                                  const Session *session,
                                                  void *before, void *end,
                                                  int sync) {
+  auto net = reinterpret_cast<MNN::Interpreter const *>(interpreter);
+  auto sess = reinterpret_cast<MNN::Session const *>(session);
+  // The backend of the op is the backend of the first tensor passed to the
+  // callback (inputs for before and outputs for end)
+  auto forwardTypeOf = [net, sess](const std::vector<MNN::Tensor *> &tensors) {
+    if (tensors.empty()) {
+      return -1;
+    }
+    return Backend_getType(reinterpret_cast<const Backend *>(
+        net->getBackend(sess, tensors[0])));
+  };
   MNN::TensorCallBackWithInfo beforeCpp =
-      [before](const std::vector<MNN::Tensor *> &tensors,
-               const MNN::OperatorInfo *op) {
+      [before, forwardTypeOf](const std::vector<MNN::Tensor *> &tensors,
+                              const MNN::OperatorInfo *op) {
         if (before == nullptr) {
           return true;
         }
         return static_cast<bool>(rust_closure_callback_runner_op(
             before, reinterpret_cast<Tensor *const *>(tensors.data()),
-            tensors.size(), reinterpret_cast<const void *>(op)));
+            tensors.size(), reinterpret_cast<const void *>(op),
+            forwardTypeOf(tensors)));
       };
   MNN::TensorCallBackWithInfo endCpp =
-      [end](const std::vector<MNN::Tensor *> &tensors,
-            const MNN::OperatorInfo *op) {
+      [end, forwardTypeOf](const std::vector<MNN::Tensor *> &tensors,
+                           const MNN::OperatorInfo *op) {
         if (end == nullptr) {
           return true;
         }
         return static_cast<bool>(rust_closure_callback_runner_op(
             end, reinterpret_cast<Tensor *const *>(tensors.data()),
-            tensors.size(), reinterpret_cast<const void *>(op)));
+            tensors.size(), reinterpret_cast<const void *>(op),
+            forwardTypeOf(tensors)));
       };
-  auto net = reinterpret_cast<MNN::Interpreter const *>(interpreter);
-  auto sess = reinterpret_cast<MNN::Session const *>(session);
   auto ret = net->runSessionWithCallBackInfo(sess, beforeCpp, endCpp,
                                              static_cast<bool>(sync));
   return static_cast<ErrorCode>(ret);
@@ -279,6 +343,26 @@ int Interpreter_getSessionInfo(Interpreter *interpreter, const Session *session,
       mnn_session, static_cast<MNN::Interpreter::SessionInfoCode>(code), ptr);
   return static_cast<int>(ret);
 }
+int Interpreter_getSessionBackends(Interpreter *interpreter,
+                                   const Session *session, int *backends,
+                                   size_t capacity) {
+  auto mnn_interpreter = reinterpret_cast<MNN::Interpreter *>(interpreter);
+  auto mnn_session = reinterpret_cast<const MNN::Session *>(session);
+  // MNN writes one entry per runtime without a bound. The runtimes of a
+  // session are keyed by forward type, so there is at most one per type plus
+  // the backup runtime.
+  std::vector<int> all(kMaxForwardTypes + 1, -1);
+  if (!mnn_interpreter->getSessionInfo(mnn_session, MNN::Interpreter::BACKENDS,
+                                       all.data())) {
+    return -1;
+  }
+  // Forward types are never negative so the entries MNN didn't write are
+  // still -1
+  auto count = static_cast<size_t>(std::find(all.begin(), all.end(), -1) -
+                                   all.begin());
+  std::copy_n(all.begin(), std::min(count, capacity), backends);
+  return static_cast<int>(count);
+}
 TensorInfoArray const *
 Interpreter_getSessionOutputAll(const Interpreter *interpreter,
                                 const Session *session) {
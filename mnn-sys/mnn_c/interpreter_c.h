@@ -55,12 +55,8 @@ enum RuntimeStatus {
 //   MNNBackendConfig *backendConfig;
 // } ScheduleConfig;
 
-#if 0
-typedef struct {
-  std::map<MNNForwardType, std::shared_ptr<Runtime>> *runtimeMap;
-  std::shared_ptr<Runtime> *defaultRuntime;
-} RuntimeInfo;
-#endif
+/** Opaque handle to MNN::RuntimeInfo */
+typedef struct RuntimeInfo RuntimeInfo;
 
 void modelPrintIO(const char *model);
 
@@ -131,25 +127,53 @@ typedef enum {
 void Interpreter_setSessionMode(Interpreter *interpreter, SessionMode mode);
 void Interpreter_setCacheFile(Interpreter *interpreter, const char *cacheFile,
                               size_t keySize);
+/** The default flag of MNN::Interpreter::setExternalFile */
+#define EXTERNAL_FILE_FLAG_DEFAULT 128
 void Interpreter_setExternalFile(Interpreter *interpreter, const char *file,
                                  size_t flag);
 ErrorCode Interpreter_updateCacheFile(Interpreter *interpreter,
                                       Session *session);
 void Interpreter_setSessionHint(Interpreter *interpreter, int mode, int value);
-// RuntimeInfo *Interpreter_createRuntime(const ScheduleConfig *configs,
-//                                        size_t configSize);
+void Interpreter_setSessionHintArray(Interpreter *interpreter, int mode,
+                                     const int *value, size_t size);
+/**
+ * @brief create runtime info which can be shared by multiple sessions
+ * @param configs   schedule configs of the runtime.
+ * @param configSize    number of schedule configs.
+ * @return created runtime info if success, NULL otherwise.
+ */
+RuntimeInfo *Interpreter_createRuntime(const MNNScheduleConfig *const *configs,
+                                       size_t configSize);
+void RuntimeInfo_destroy(RuntimeInfo *runtime);
+/** Returns 1 if the runtime contains a backend of type `forwardType` */
+int RuntimeInfo_hasBackend(const RuntimeInfo *runtime, int forwardType);
+/**
+ * @brief load a tuning cache into the backend of type `forwardType`.
+ * The buffer must outlive the runtime.
+ * @return 1 on success, 0 if the backend is missing or rejected the cache.
+ */
+int RuntimeInfo_setCache(RuntimeInfo *runtime, int forwardType,
+                         const void *buffer, size_t size);
+/**
+ * @brief get the tuning cache of the backend of type `forwardType`.
+ * The returned buffer is owned by the runtime and only valid until the next
+ * call.
+ * @return null if the backend is missing or has no cache.
+ */
+const void *RuntimeInfo_getCache(RuntimeInfo *runtime, int forwardType,
+                                 size_t *size);
 Session *Interpreter_createSession(Interpreter *interpreter,
                                    const MNNScheduleConfig *config);
-// Session *Interpreter_createSessionWithRuntime(Interpreter *interpreter,
-//                                               const ScheduleConfig *config,
-//                                               const RuntimeInfo *runtime);
+Session *Interpreter_createSessionWithRuntime(Interpreter *interpreter,
+                                              const MNNScheduleConfig *config,
+                                              const RuntimeInfo *runtime);
 Session *
 Interpreter_createMultiPathSession(Interpreter *interpreter,
                                    const MNNScheduleConfig *const *configs,
                                    size_t configSize);
-// Session *Interpreter_createMultiPathSessionWithRuntime(
-//     Interpreter *interpreter, const ScheduleConfig *configs, size_t
-//     configSize, const RuntimeInfo *runtime);
+Session *Interpreter_createMultiPathSessionWithRuntime(
+    Interpreter *interpreter, const MNNScheduleConfig *const *configs,
+    size_t configSize, const RuntimeInfo *runtime);
 int Interpreter_releaseSession(Interpreter *interpreter, Session *session);
 void Interpreter_resizeSession(Interpreter *interpreter, Session *session);
 void Interpreter_resizeSessionWithFlag(Interpreter *interpreter,
@@ -191,6 +215,22 @@ void Interpreter_resizeTensorByNCHW(Interpreter *interpreter, Tensor *tensor,
 const Backend *Interpreter_getBackend(const Interpreter *interpreter,
                                       const Session *session,
                                       const Tensor *tensor);
+/**
+ * @brief get the forward type of the backend.
+ * @return the MNNForwardType of the backend, -1 if the backend is NULL.
+ * @note implemented in internal_c.cpp since it needs the private headers
+ */
+int Backend_getType(const Backend *backend);
+/**
+ * @brief get the forward types of the runtimes of the session, this includes
+ * the backup CPU runtime.
+ * @param backends filled with at most capacity forward types
+ * @return the number of runtimes of the session, which can be larger than
+ * capacity, -1 on failure
+ */
+int Interpreter_getSessionBackends(Interpreter *interpreter,
+                                   const Session *session, int *backends,
+                                   size_t capacity);
 const char *Interpreter_bizCode(const Interpreter *interpreter);
 const char *Interpreter_uuid(const Interpreter *interpreter);
 
@@ -0,0 +1,242 @@
+#include "model_info_c.h"
+#include "MNN/Interpreter.hpp"
+#include "MNN_generated.h"
+#include <cstdlib>
+#include <set>
+#include <utility>
+#include <vector>
+// Reads the flatbuffer of the model directly, this is the only shim which
+// depends on the schema of the model file.
+extern "C" {
+static const MNN::Net *getVerifiedNet(const Interpreter *interpreter) {
+  auto mnn_interpreter =
+      reinterpret_cast<MNN::Interpreter const *>(interpreter);
+  auto buffer = mnn_interpreter->getModelBuffer();
+  if (buffer.first == nullptr || buffer.second == 0) {
+    return nullptr;
+  }
+  flatbuffers::Verifier verifier(
+      reinterpret_cast<const uint8_t *>(buffer.first), buffer.second);
+  if (!MNN::VerifyNetBuffer(verifier)) {
+    return nullptr;
+  }
+  return MNN::GetNet(buffer.first);
+}
+static bool dataTypeToHalide(MNN::DataType dtype, halide_type_t *type) {
+  switch (dtype) {
+  case MNN::DataType_DT_FLOAT:
+    *type = halide_type_of<float>();
+    return true;
+  case MNN::DataType_DT_DOUBLE:
+    *type = halide_type_of<double>();
+    return true;
+  case MNN::DataType_DT_INT32:
+    *type = halide_type_of<int32_t>();
+    return true;
+  case MNN::DataType_DT_INT64:
+    *type = halide_type_of<int64_t>();
+    return true;
+  case MNN::DataType_DT_INT16:
+    *type = halide_type_of<int16_t>();
+    return true;
+  case MNN::DataType_DT_INT8:
+    *type = halide_type_of<int8_t>();
+    return true;
+  case MNN::DataType_DT_UINT8:
+    *type = halide_type_of<uint8_t>();
+    return true;
+  case MNN::DataType_DT_BOOL:
+    *type = halide_type_of<bool>();
+    return true;
+  case MNN::DataType_DT_HALF:
+    *type = halide_type_t(halide_type_float, 16);
+    return true;
+  case MNN::DataType_DT_BFLOAT16:
+    *type = halide_type_t(halide_type_bfloat, 16);
+    return true;
+  default:
+    return false;
+  }
+}
+static bool dataFormatToDimensionType(MNN::MNN_DATA_FORMAT format,
+                                      DimensionType *type) {
+  switch (format) {
+  case MNN::MNN_DATA_FORMAT_NCHW:
+    *type = CAFFE;
+    return true;
+  case MNN::MNN_DATA_FORMAT_NC4HW4:
+    *type = CAFFE_C4;
+    return true;
+  case MNN::MNN_DATA_FORMAT_NHWC:
+    *type = TENSORFLOW;
+    return true;
+  default:
+    return false;
+  }
+}
+static TensorDescriptorArray *createTensorDescriptorArray(size_t count) {
+  auto array =
+      (TensorDescriptorArray *)malloc(sizeof(TensorDescriptorArray));
+  array->size = count;
+  array->tensors =
+      (TensorDescriptor *)calloc(count == 0 ? 1 : count, sizeof(TensorDescriptor));
+  return array;
+}
+static void setDescriptorName(TensorDescriptor *descriptor,
+                              const flatbuffers::String *name) {
+  if (name == nullptr) {
+    descriptor->name = createCString("", 0);
+  } else {
+    descriptor->name = createCString(name->c_str(), name->size());
+  }
+}
+static void setDescriptorShape(TensorDescriptor *descriptor,
+                               const flatbuffers::Vector<int> *dims) {
+  if (dims == nullptr) {
+    return;
+  }
+  descriptor->shapeSize = dims->size();
+  descriptor->shape = (int *)malloc(sizeof(int) * (descriptor->shapeSize + 1));
+  for (size_t d = 0; d < descriptor->shapeSize; d++) {
+    descriptor->shape[d] = dims->Get(d);
+  }
+  descriptor->hasShape = 1;
+}
+static void describeInput(TensorDescriptor *descriptor,
+                          const MNN::Input *input) {
+  setDescriptorShape(descriptor, input->dims());
+  descriptor->hasType = dataTypeToHalide(input->dtype(), &descriptor->type);
+  descriptor->hasDimensionType =
+      dataFormatToDimensionType(input->dformat(), &descriptor->dimensionType);
+}
+static const flatbuffers::String *tensorName(const MNN::Net *net, int index) {
+  if (net->tensorName() != nullptr && index >= 0 &&
+      (flatbuffers::uoffset_t)index < net->tensorName()->size()) {
+    return net->tensorName()->Get(index);
+  }
+  return nullptr;
+}
+static TensorDescriptorArray *describeInputs(const MNN::Net *net) {
+  std::vector<const MNN::Op *> inputs;
+  for (auto op : *net->oplists()) {
+    if (op->type() == MNN::OpType_Input && op->outputIndexes() != nullptr &&
+        op->outputIndexes()->size() > 0) {
+      inputs.push_back(op);
+    }
+  }
+  auto array = createTensorDescriptorArray(inputs.size());
+  for (size_t i = 0; i < inputs.size(); i++) {
+    auto op = inputs[i];
+    auto descriptor = &array->tensors[i];
+    auto name = tensorName(net, op->outputIndexes()->Get(0));
+    setDescriptorName(descriptor, name != nullptr ? name : op->name());
+    auto input = op->main_as_Input();
+    if (input != nullptr) {
+      describeInput(descriptor, input);
+    }
+  }
+  return array;
+}
+// Outputs only have a declared shape and type if the model stores a
+// description of the tensor, e.g. static models, or if the output is an input
+static void describeOutput(TensorDescriptor *descriptor, const MNN::Net *net,
+                           int index) {
+  if (index < 0) {
+    return;
+  }
+  if (net->extraTensorDescribe() != nullptr) {
+    for (auto describe : *net->extraTensorDescribe()) {
+      if (describe->index() != index || describe->blob() == nullptr) {
+        continue;
+      }
+      auto blob = describe->blob();
+      setDescriptorShape(descriptor, blob->dims());
+      descriptor->hasType =
+          dataTypeToHalide(blob->dataType(), &descriptor->type);
+      descriptor->hasDimensionType = dataFormatToDimensionType(
+          blob->dataFormat(), &descriptor->dimensionType);
+      return;
+    }
+  }
+  for (auto op : *net->oplists()) {
+    if (op->type() != MNN::OpType_Input || op->outputIndexes() == nullptr ||
+        op->outputIndexes()->size() == 0 ||
+        op->outputIndexes()->Get(0) != index) {
+      continue;
+    }
+    auto input = op->main_as_Input();
+    if (input != nullptr) {
+      describeInput(descriptor, input);
+    }
+    return;
+  }
+}
+static TensorDescriptorArray *describeOutputs(const MNN::Net *net) {
+  // The tensor index of every output, -1 if the name isn't a tensor of the net
+  std::vector<std::pair<const flatbuffers::String *, int>> outputs;
+  if (net->outputName() != nullptr && net->outputName()->size() > 0) {
+    for (auto name : *net->outputName()) {
+      int index = -1;
+      if (net->tensorName() != nullptr) {
+        for (flatbuffers::uoffset_t i = 0; i < net->tensorName()->size(); i++) {
+          if (net->tensorName()->Get(i)->str() == name->str()) {
+            index = (int)i;
+            break;
+          }
+        }
+      }
+      outputs.emplace_back(name, index);
+    }
+  } else if (net->tensorName() != nullptr) {
+    // Same as MNN, any tensor which is produced but never consumed is an
+    // output
+    std::set<int> consumed;
+    for (auto op : *net->oplists()) {
+      if (op->inputIndexes() != nullptr) {
+        for (auto index : *op->inputIndexes()) {
+          consumed.insert(index);
+        }
+      }
+    }
+    for (auto op : *net->oplists()) {
+      if (op->outputIndexes() == nullptr) {
+        continue;
+      }
+      for (auto index : *op->outputIndexes()) {
+        auto name = tensorName(net, index);
+        if (consumed.find(index) == consumed.end() && name != nullptr) {
+          outputs.emplace_back(name, index);
+        }
+      }
+    }
+  }
+  auto array = createTensorDescriptorArray(outputs.size());
+  for (size_t i = 0; i < outputs.size(); i++) {
+    setDescriptorName(&array->tensors[i], outputs[i].first);
+    describeOutput(&array->tensors[i], net, outputs[i].second);
+  }
+  return array;
+}
+int Interpreter_describeModel(const Interpreter *interpreter,
+                              TensorDescriptorArray **inputs,
+                              TensorDescriptorArray **outputs) {
+  auto net = getVerifiedNet(interpreter);
+  if (net == nullptr || net->oplists() == nullptr) {
+    return 0;
+  }
+  *inputs = describeInputs(net);
+  *outputs = describeOutputs(net);
+  return 1;
+}
+void destroyTensorDescriptorArray(TensorDescriptorArray *array) {
+  if (array == nullptr) {
+    return;
+  }
+  for (size_t i = 0; i < array->size; i++) {
+    destroyCString(&array->tensors[i].name);
+    free(array->tensors[i].shape);
+  }
+  free(array->tensors);
+  free(array);
+}
+} // extern "C"
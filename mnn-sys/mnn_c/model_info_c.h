@@ -0,0 +1,49 @@
+#ifndef MODEL_INFO_C_H
+#define MODEL_INFO_C_H
+#include "interpreter_c.h"
+#include "tensor_c.h"
+#include "utils.h"
+#include <MNN/HalideRuntime.h>
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * Description of an input / output tensor as declared in the model file.
+ * Fields which the model does not declare are flagged with has* = 0, outputs
+ * are described by the tensor descriptions stored in the model, if any.
+ */
+typedef struct {
+  CString name;
+  int *shape;
+  size_t shapeSize;
+  int hasShape;
+  struct halide_type_t type;
+  int hasType;
+  DimensionType dimensionType;
+  int hasDimensionType;
+} TensorDescriptor;
+
+typedef struct {
+  TensorDescriptor *tensors;
+  size_t size;
+} TensorDescriptorArray;
+
+/**
+ * @brief describe the model inputs and outputs by reading the model buffer.
+ * The buffer is verified once per call, this does not create a session or
+ * allocate any backend memory.
+ * @param inputs set to the declared inputs on success
+ * @param outputs set to the declared outputs on success
+ * @return 0 if the model buffer was released or is invalid, 1 otherwise.
+ */
+int Interpreter_describeModel(const Interpreter *interpreter,
+                              TensorDescriptorArray **inputs,
+                              TensorDescriptorArray **outputs);
+void destroyTensorDescriptorArray(TensorDescriptorArray *array);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // MODEL_INFO_C_H
@@ -25,6 +25,7 @@ void mnnsc_set_backend_config(MNNScheduleConfig *config,
                               MNNBackendConfig *backendConfig);
 MNNForwardType mnnsc_get_type(MNNScheduleConfig *config);
 MNNForwardType mnnsc_get_backup_type(MNNScheduleConfig *config);
+int mnnsc_get_num_threads(MNNScheduleConfig *config);
 
 #ifdef __cplusplus
 }
@@ -73,18 +73,19 @@ uint64_t Tensor_deviceId(const Tensor *tensor) {
 int Tensor_dimensions(const Tensor *tensor) {
   return reinterpret_cast<const MNN::Tensor *>(tensor)->dimensions();
 }
-/**
- * @brief get all dimensions' extent.
- * @return dimensions' extent.
- */
-TensorShape Tensor_shape(const Tensor *tensor) {
+int Tensor_shape(const Tensor *tensor, TensorShape *shape) {
   auto shapeVec = reinterpret_cast<const MNN::Tensor *>(tensor)->shape();
-  TensorShape shape;
-  shape.size = shapeVec.size();
-  for (size_t i = 0; i < shapeVec.size(); i++) {
-    shape.shape[i] = shapeVec[i];
+  if (shapeVec.size() > TENSOR_SHAPE_MAX_DIMS) {
+    return 0;
   }
-  return shape;
+  shape->size = shapeVec.size();
+  for (size_t i = 0; i < shape->size; i++) {
+    shape->shape[i] = shapeVec[i];
+  }
+  for (size_t i = shape->size; i < TENSOR_SHAPE_MAX_DIMS; i++) {
+    shape->shape[i] = 1;
+  }
+  return 1;
 }
 
 int Tensor_size(const Tensor *tensor) {
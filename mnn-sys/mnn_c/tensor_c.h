@@ -9,8 +9,11 @@
 extern "C" {
 #endif
 typedef struct Tensor Tensor;
+// Maximum rank a TensorShape can carry, this matches MNN_MAX_TENSOR_DIM which
+// is checked in internal_c.cpp
+#define TENSOR_SHAPE_MAX_DIMS 8
 typedef struct {
-  int shape[4];
+  int shape[TENSOR_SHAPE_MAX_DIMS];
   size_t size;
 } TensorShape;
 typedef enum { TENSORFLOW, CAFFE, CAFFE_C4 } DimensionType;
@@ -31,13 +34,39 @@ int Tensor_copyToHostTensor(const Tensor *deviceTensor, Tensor *hostTensor);
 Tensor *Tensor_createHostTensorFromDevice(const Tensor *deviceTensor,
                                           int copyData);
 DimensionType Tensor_getDimensionType(const Tensor *tensor);
+/**
+ * @brief get the memory layout of the tensor, unlike Tensor_getDimensionType
+ * this reports CAFFE_C4 for NC4HW4 tensors.
+ * @note implemented in internal_c.cpp since it needs the private headers
+ */
+DimensionType Tensor_getDataFormat(const Tensor *tensor);
+/**
+ * @brief get the forward type of the backend the tensor is allocated on.
+ * @return the MNNForwardType of the backend, -1 if the tensor has no backend,
+ * e.g. host tensors created on their own.
+ * @note implemented in internal_c.cpp since it needs the private headers
+ */
+int Tensor_getBackendType(const Tensor *tensor);
+/**
+ * @brief convert the host tensor src into the host tensor dst with the CPU
+ * converter of MNN, the tensors have the same type and shape in their own
+ * layouts.
+ * @return 1 on success, 0 if MNN can't convert the type, e.g. 64 bit types
+ * @note implemented in internal_c.cpp since it needs the private headers
+ */
+int Tensor_convertLayout(const Tensor *src, Tensor *dst);
 const halide_buffer_t *Tensor_buffer(const Tensor *tensor);
 halide_buffer_t *Tensor_buffer_mut(Tensor *tensor);
 const void *Tensor_host(const Tensor *tensor);
 void *Tensor_host_mut(Tensor *tensor);
 uint64_t Tensor_deviceId(const Tensor *tensor);
 int Tensor_dimensions(const Tensor *tensor);
-TensorShape Tensor_shape(const Tensor *tensor);
+/**
+ * @brief get all dimensions' extent.
+ * @return 0 if the tensor has more than TENSOR_SHAPE_MAX_DIMS dimensions and
+ * shape is left untouched, 1 otherwise.
+ */
+int Tensor_shape(const Tensor *tensor, TensorShape *shape);
 int Tensor_size(const Tensor *tensor);
 size_t Tensor_usize(const Tensor *tensor);
 int Tensor_elementSize(const Tensor *tensor);
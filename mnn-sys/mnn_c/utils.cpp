@@ -46,7 +46,7 @@ CString createCString(const char *str, size_t max_size) {
   CString cstr;
   // Find out the size of the input
   size_t size = 0;
-  while (str[size] != '\0' || size <= max_size) {
+  while (size < max_size && str[size] != '\0') {
     size++;
   }
   cstr.size = size;
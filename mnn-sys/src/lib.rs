@@ -67,6 +67,15 @@ halide_types! {
     i64 =>  halide_type_t::new(halide_type_code_t::halide_type_int, 64,1)
 }
 
+#[cfg(feature = "half")]
+pub use half;
+
+#[cfg(feature = "half")]
+halide_types! {
+    half::f16 => halide_type_t::new(halide_type_code_t::halide_type_float, 16, 1),
+    half::bf16 => halide_type_t::new(halide_type_code_t::halide_type_bfloat, 16, 1)
+}
+
 impl Drop for CString {
     fn drop(&mut self) {
         unsafe { destroyCString(self.as_ptr_mut()) }
@@ -0,0 +1,235 @@
+use crate::{Interpreter, RawTensor, Session, SessionMode, prelude::*};
+
+/// Host buffers of the caller bound as the inputs and outputs of a session
+///
+/// With [`SessionMode::InputUser`] and [`SessionMode::OutputUser`] set on the interpreter when
+/// the session is created, MNN doesn't allocate the input / output tensors and uses the memory set
+/// on them instead. The bindings point the tensors at slices owned by the caller, so inputs are
+/// read from and outputs are written to that memory without any copies.
+///
+/// The slices are borrowed for as long as the bindings exist and the tensors are reset when
+/// the bindings are dropped, so a session can't run on freed memory.
+///
+/// ```no_run
+/// # use mnn::*;
+/// # fn main() -> Result<()> {
+/// let mut net = Interpreter::from_file("model.mnn")?;
+/// net.set_session_mode(SessionMode::InputUser);
+/// net.set_session_mode(SessionMode::OutputUser);
+/// let mut session = net.create_session(ScheduleConfig::new())?;
+/// let input = vec![0.0f32; 3 * 64 * 64];
+/// let mut output = vec![0.0f32; 3 * 256 * 256];
+/// let mut bindings = net.bind(&mut session);
+/// bindings.bind_input("input", &input)?;
+/// bindings.bind_output("output", &mut output)?;
+/// bindings.run()?;
+/// drop(bindings);
+/// println!("{:?}", &output[..10]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The buffers have to outlive the bindings
+/// ```compile_fail
+/// # use mnn::*;
+/// # fn main() -> Result<()> {
+/// # let mut net = Interpreter::from_file("model.mnn")?;
+/// # let mut session = net.create_session(ScheduleConfig::new())?;
+/// let mut bindings = net.bind(&mut session);
+/// {
+///     let input = vec![0.0f32; 3 * 64 * 64];
+///     bindings.bind_input("input", &input)?;
+/// }
+/// bindings.run()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SessionBindings<'s, 'b> {
+    interpreter: &'s Interpreter,
+    session: &'s mut Session,
+    bound: Vec<Binding>,
+    needs_resize: bool,
+    __marker: PhantomData<&'b mut [u8]>,
+}
+
+/// A tensor pointed at a buffer of the caller
+#[derive(Debug)]
+struct Binding {
+    name: String,
+    tensor: *mut mnn_sys::Tensor,
+    host: *mut u8,
+    /// The memory of the tensor before it was bound
+    previous: *mut u8,
+    /// The session was resized since the tensor was bound, so `previous` may have been freed
+    resized: bool,
+}
+
+impl core::fmt::Debug for SessionBindings<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SessionBindings")
+            .field("session", &self.session)
+            .field("bound", &self.bound)
+            .finish()
+    }
+}
+
+impl Interpreter {
+    /// Bind buffers of the caller as inputs and outputs of the session, see [`SessionBindings`]
+    pub fn bind<'s, 'b>(&'s self, session: &'s mut Session) -> SessionBindings<'s, 'b> {
+        SessionBindings {
+            interpreter: self,
+            session,
+            bound: Vec::new(),
+            needs_resize: false,
+            __marker: PhantomData,
+        }
+    }
+}
+
+impl<'b> SessionBindings<'_, 'b> {
+    /// Use `data` as the input tensor `name`
+    ///
+    /// Requires [`SessionMode::InputUser`]. The length of `data` has to match the current shape
+    /// of the input, use [`Interpreter::resize_tensor`] before binding to change it.
+    pub fn bind_input<H: HalideType>(
+        &mut self,
+        name: impl AsRef<str>,
+        data: &'b [H],
+    ) -> Result<&mut Self> {
+        let name = name.as_ref();
+        ensure!(
+            self.session.has_mode(SessionMode::InputUser),
+            ErrorKind::NotSupported;
+            "Binding inputs requires a session created with SessionMode::InputUser"
+        );
+        let tensor = self.interpreter.raw_input(self.session, name)?.inner;
+        self.bind::<H>(name, tensor, data.as_ptr().cast_mut().cast(), data.len())?;
+        Ok(self)
+    }
+
+    /// Use `data` as the output tensor `name`, the outputs are written directly into it
+    ///
+    /// Requires [`SessionMode::OutputUser`]
+    pub fn bind_output<H: HalideType>(
+        &mut self,
+        name: impl AsRef<str>,
+        data: &'b mut [H],
+    ) -> Result<&mut Self> {
+        let name = name.as_ref();
+        ensure!(
+            self.session.has_mode(SessionMode::OutputUser),
+            ErrorKind::NotSupported;
+            "Binding outputs requires a session created with SessionMode::OutputUser"
+        );
+        let tensor = self.interpreter.raw_output(self.session, name)?.inner;
+        self.bind::<H>(name, tensor, data.as_mut_ptr().cast(), data.len())?;
+        Ok(self)
+    }
+
+    /// Run the session on the bound buffers
+    ///
+    /// The session is resized after the bindings changed. Returns an error if the backend
+    /// replaced one of the bound buffers with its own memory, e.g. for GPU backends.
+    pub fn run(&mut self) -> Result<()> {
+        if self.needs_resize {
+            self.interpreter.resize_session(self.session);
+            for binding in &mut self.bound {
+                binding.resized = true;
+                let host = unsafe { (*mnn_sys::Tensor_buffer(binding.tensor)).host };
+                ensure!(
+                    host == binding.host,
+                    ErrorKind::NotSupported;
+                    format!("The backend allocated its own memory for tensor \"{}\"", binding.name)
+                );
+            }
+            self.needs_resize = false;
+        }
+        self.interpreter.run_session(self.session)?;
+        self.interpreter.wait(self.session);
+        Ok(())
+    }
+
+    /// The session the buffers are bound to
+    pub fn session(&self) -> &Session {
+        self.session
+    }
+
+    fn bind<H: HalideType>(
+        &mut self,
+        name: &str,
+        tensor: *mut mnn_sys::Tensor,
+        host: *mut u8,
+        len: usize,
+    ) -> Result<()> {
+        let tensor = RawTensor::from_ptr(tensor);
+        ensure!(
+            tensor.is_type_of::<H>(),
+            ErrorKind::HalideTypeMismatch {
+                got: std::any::type_name::<H>(),
+            };
+            format!("Tensor \"{name}\" is of type {:?}", tensor.get_type())
+        );
+        ensure!(
+            !tensor.is_dynamic_unsized(),
+            ErrorKind::DynamicTensorError;
+            format!("Tensor \"{name}\" needs a shape")
+        );
+        ensure!(
+            tensor.element_size() == len,
+            ErrorKind::SizeMismatch {
+                expected: tensor.element_size(),
+                got: len,
+            };
+            format!("Tensor \"{name}\" has shape {:?}", tensor.shape())
+        );
+        let buffer = unsafe { mnn_sys::Tensor_buffer_mut(tensor.inner) };
+        ensure!(!buffer.is_null(), ErrorKind::TensorError; format!("Tensor \"{name}\" has no buffer"));
+        // Rebinding a tensor keeps the memory it had before the first binding
+        let (previous, resized) = match self.bound.iter().position(|b| b.tensor == tensor.inner) {
+            Some(index) => {
+                let binding = self.bound.swap_remove(index);
+                (binding.previous, binding.resized)
+            }
+            None => (unsafe { (*buffer).host }, false),
+        };
+        unsafe { (*buffer).host = host };
+        self.bound.push(Binding {
+            name: name.to_owned(),
+            tensor: tensor.inner,
+            host,
+            previous,
+            resized,
+        });
+        self.needs_resize = true;
+        Ok(())
+    }
+}
+
+impl Drop for SessionBindings<'_, '_> {
+    /// Reset the tensors so the session doesn't keep pointers to the buffers
+    ///
+    /// The memory a tensor had before it was bound is only restored if the session wasn't resized
+    /// since, the resize may have freed it. Otherwise the tensor is left without memory and the
+    /// session is reallocated, so the backend allocates the tensors it owns again.
+    fn drop(&mut self) {
+        self.session.wait_pending();
+        let mut resize = false;
+        for binding in self.bound.drain(..).rev() {
+            let buffer = unsafe { mnn_sys::Tensor_buffer_mut(binding.tensor) };
+            // The backend reallocated the tensor, it doesn't point at the buffer anymore
+            if unsafe { (*buffer).host } != binding.host {
+                continue;
+            }
+            let host = if binding.resized {
+                resize = true;
+                core::ptr::null_mut()
+            } else {
+                binding.previous
+            };
+            unsafe { (*buffer).host = host };
+        }
+        if resize {
+            self.interpreter.resize_session_reallocate(self.session);
+        }
+    }
+}
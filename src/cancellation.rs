@@ -0,0 +1,51 @@
+//! Cooperative cancellation of a running session
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+/// A token used to stop a session running with
+/// [`Interpreter::run_session_with_cancellation`](crate::Interpreter::run_session_with_cancellation)
+///
+/// The token is cheap to clone and all clones share the same state, so it can be handed to
+/// another thread (e.g. a ui thread) which calls [`CancellationToken::cancel`].
+///
+/// The token is checked after every operator, so the session stops once the currently running
+/// operator has finished.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a new token which is not cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the cancellation of every session run with this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /// Returns true if [`CancellationToken::cancel`] was called
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Reset the token so it can be used for another run
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Release);
+    }
+}
+
+#[test]
+fn test_cancellation_token_is_shared() {
+    let token = CancellationToken::new();
+    let other = token.clone();
+    assert!(!token.is_cancelled());
+    std::thread::spawn(move || other.cancel()).join().unwrap();
+    assert!(token.is_cancelled());
+    token.reset();
+    assert!(!token.is_cancelled());
+}
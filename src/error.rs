@@ -26,9 +26,33 @@ impl std::error::Error for MNNError {}
 /// Error types for MNN
 #[derive(thiserror::Error, Debug)]
 pub enum ErrorKind {
-    /// Internal error (from MNN library)
-    #[error("Internal error: {0:?}")]
-    InternalError(ErrorCode),
+    /// MNN ran out of memory
+    #[error("Out of memory")]
+    OutOfMemory,
+    /// The operation is not supported by MNN or the backend
+    #[error("Not supported")]
+    NotSupported,
+    /// MNN failed to compute the size of a tensor, usually because of invalid input shapes
+    #[error("Failed to compute tensor size")]
+    ComputeSizeError,
+    /// There is no execution for an operator on the backend
+    #[error("No execution")]
+    NoExecution,
+    /// An invalid value was passed to MNN
+    #[error("Invalid value")]
+    InvalidValue,
+    /// The input data is invalid
+    #[error("Invalid input data")]
+    InputDataError,
+    /// The session was interrupted by an operator callback
+    #[error("Session interrupted by callback")]
+    CallbackStopped,
+    /// The tensor is not supported by the operator
+    #[error("Tensor not supported")]
+    TensorNotSupported,
+    /// The tensor needs to be divided before it can be used by the operator
+    #[error("Tensor needs to be divided")]
+    TensorNeedDivide,
     /// Mismatching Size for input
     #[error("Invalid input: expected {expected}, got {got}")]
     SizeMismatch {
@@ -67,6 +91,26 @@ pub enum ErrorKind {
     /// Tried to run a dynamic tensor without resizing it first
     #[error("Dynamic Tensor Error: Tensor needs to be resized before using")]
     DynamicTensorError,
+    /// The session hint has an invalid value or conflicts with the session mode
+    #[error("Invalid session hint")]
+    InvalidSessionHint,
+    /// The shape has more dimensions than a tensor can hold
+    #[error("Too many dimensions: at most {max} are supported, got {got}")]
+    TooManyDimensions {
+        /// Maximum supported number of dimensions
+        max: usize,
+        /// Provided number of dimensions
+        got: usize,
+    },
+    /// The cache blob is malformed
+    #[error("Invalid cache")]
+    InvalidCache,
+    /// The cache blob was created for a different model, backend or MNN version
+    #[error("Stale cache")]
+    StaleCache,
+    /// The session was stopped by a [`CancellationToken`](crate::CancellationToken)
+    #[error("Session run was cancelled")]
+    Cancelled,
 }
 
 impl MNNError {
@@ -77,9 +121,13 @@ impl MNNError {
         Self { kind }
     }
 
+    /// Convert the error code returned by MNN, [`ErrorCode::ERROR_CODE_NO_ERROR`] is `Ok`
     #[track_caller]
-    pub(crate) fn from_error_code(code: ErrorCode) -> Self {
-        Self::new(ErrorKind::InternalError(code))
+    pub(crate) fn from_error_code(code: ErrorCode) -> Result<()> {
+        match ErrorKind::from_error_code(code) {
+            Some(kind) => Err(Self::new(kind).attach_printable(format!("MNN error code {code:?}"))),
+            None => Ok(()),
+        }
     }
 
     /// Return the inner [error_stack::Report] containing the error
@@ -87,6 +135,41 @@ impl MNNError {
     pub fn into_inner(self) -> error_stack::Report<ErrorKind> {
         self.kind
     }
+
+    /// Return the kind of the error
+    pub fn kind(&self) -> &ErrorKind {
+        self.kind.current_context()
+    }
+
+    /// Returns true if running the same operation again may succeed
+    ///
+    /// This is the case when MNN ran out of memory (e.g. after freeing other sessions) or when
+    /// the session was interrupted by a callback or a [`CancellationToken`](crate::CancellationToken).
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::OutOfMemory | ErrorKind::CallbackStopped | ErrorKind::Cancelled
+        )
+    }
+}
+
+impl ErrorKind {
+    /// Map an MNN error code to the matching error kind, `None` for
+    /// [`ErrorCode::ERROR_CODE_NO_ERROR`]
+    pub fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code {
+            ErrorCode::ERROR_CODE_NO_ERROR => None,
+            ErrorCode::ERROR_CODE_OUT_OF_MEMORY => Some(Self::OutOfMemory),
+            ErrorCode::ERROR_CODE_NOT_SUPPORT => Some(Self::NotSupported),
+            ErrorCode::ERROR_CODE_COMPUTE_SIZE_ERROR => Some(Self::ComputeSizeError),
+            ErrorCode::ERROR_CODE_NO_EXECUTION => Some(Self::NoExecution),
+            ErrorCode::ERROR_CODE_INVALID_VALUE => Some(Self::InvalidValue),
+            ErrorCode::ERROR_CODE_INPUT_DATA_ERROR => Some(Self::InputDataError),
+            ErrorCode::ERROR_CODE_CALL_BACK_STOP => Some(Self::CallbackStopped),
+            ErrorCode::ERROR_CODE_TENSOR_NOT_SUPPORT => Some(Self::TensorNotSupported),
+            ErrorCode::ERROR_CODE_TENSOR_NEED_DIVIDE => Some(Self::TensorNeedDivide),
+        }
+    }
 }
 
 impl From<ErrorKind> for MNNError {
@@ -155,3 +238,14 @@ impl MNNError {
         Self { kind }
     }
 }
+
+#[test]
+fn test_error_code_mapping() {
+    assert!(MNNError::from_error_code(ErrorCode::ERROR_CODE_NO_ERROR).is_ok());
+    let err = MNNError::from_error_code(ErrorCode::ERROR_CODE_OUT_OF_MEMORY).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfMemory));
+    assert!(err.is_retryable());
+    let err = MNNError::from_error_code(ErrorCode::ERROR_CODE_INPUT_DATA_ERROR).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InputDataError));
+    assert!(!err.is_retryable());
+}
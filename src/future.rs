@@ -0,0 +1,245 @@
+use crate::{Interpreter, Session, prelude::*};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+
+/// A future which resolves once the outputs of a session are ready to be read
+///
+/// Returned by [`Interpreter::run_session_async`]. It doesn't depend on any async runtime, the
+/// outputs of every run are waited on by a background thread of its own, which wakes the task
+/// when they are ready.
+///
+/// Reading the outputs of the session ([`Interpreter::output`], [`Interpreter::outputs`], ...)
+/// blocks until they are ready, as does the next run, resize or drop of the session. Dropping the
+/// future doesn't cancel the computation.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct SessionFuture<'s> {
+    state: State,
+    __marker: PhantomData<&'s ()>,
+}
+
+#[derive(Debug)]
+enum State {
+    Ready(Option<Result<()>>),
+    Waiting(Arc<Pending>),
+}
+
+impl SessionFuture<'_> {
+    fn ready(result: Result<()>) -> Self {
+        Self {
+            state: State::Ready(Some(result)),
+            __marker: PhantomData,
+        }
+    }
+}
+
+impl Future for SessionFuture<'_> {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        match &mut this.state {
+            State::Ready(result) => Poll::Ready(
+                result
+                    .take()
+                    .expect("SessionFuture polled after completion"),
+            ),
+            State::Waiting(pending) => match pending.poll(cx) {
+                Poll::Ready(()) => {
+                    this.state = State::Ready(None);
+                    Poll::Ready(Ok(()))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+/// Completion of an asynchronous run, shared by the future, the session and the waiting thread
+#[derive(Debug, Default)]
+pub(crate) struct Pending {
+    state: Mutex<PendingState>,
+    finished: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct PendingState {
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+impl Pending {
+    /// A panic while holding the lock can't leave the state inconsistent
+    fn lock(&self) -> std::sync::MutexGuard<'_, PendingState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn finish(&self) {
+        let waker = {
+            let mut state = self.lock();
+            state.finished = true;
+            state.waker.take()
+        };
+        self.finished.notify_all();
+        // Woken outside the lock in case the executor polls right away
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn poll(&self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.lock();
+        if state.finished {
+            return Poll::Ready(());
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Block until the outputs are ready
+    pub(crate) fn wait(&self) {
+        let mut state = self.lock();
+        while !state.finished {
+            state = self
+                .finished
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+/// Output tensors of a session which are waited on by the waiting thread
+struct Outputs(Vec<*mut mnn_sys::Tensor>);
+
+// SAFETY: The tensors are owned by the session, which waits for the job to finish before it's
+// run again, resized, released or its outputs are read.
+unsafe impl Send for Outputs {}
+
+impl Outputs {
+    fn wait(&self) {
+        self.0.iter().for_each(|&tensor| unsafe {
+            mnn_sys::Tensor_wait(tensor, mnn_sys::MapType::MAP_TENSOR_READ, 1);
+        });
+    }
+}
+
+/// A run waited on by its own thread, it's finished when dropped so the session never waits
+/// forever even if the thread panics
+struct Job {
+    outputs: Outputs,
+    /// Sessions sharing the runtime must not use it while the outputs are waited on
+    runtime: Option<Arc<crate::Runtime>>,
+    pending: Arc<Pending>,
+}
+
+impl Job {
+    fn take(job: &Mutex<Option<Self>>) -> Option<Self> {
+        job.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+
+    fn wait(&self) {
+        let _guard = self.runtime.as_deref().map(crate::Runtime::lock);
+        self.outputs.wait();
+    }
+}
+
+impl Drop for Job {
+    fn drop(&mut self) {
+        self.pending.finish();
+    }
+}
+
+/// Whether the outputs of the backend can be waited on from another thread than the one which
+/// ran the session
+///
+/// OpenCL command queues and Metal command buffers can be waited on from any thread, the other
+/// backends are waited on by the thread which ran the session.
+fn can_wait_off_thread(backend: crate::ForwardType) -> bool {
+    match backend {
+        #[cfg(feature = "opencl")]
+        crate::ForwardType::OpenCL => true,
+        #[cfg(feature = "metal")]
+        crate::ForwardType::Metal => true,
+        _ => false,
+    }
+}
+
+impl Interpreter {
+    /// Run a session without blocking until the outputs are ready
+    ///
+    /// The session is scheduled on the calling thread and the returned future resolves once
+    /// every output can be read, so the host can prepare the next input while a GPU backend is
+    /// still computing. Every run is waited on by a thread of its own, so a slow run doesn't
+    /// delay the futures of other sessions.
+    ///
+    /// Only backends which can be waited on from another thread (OpenCL and Metal) are waited on
+    /// in the background. For the other backends, and if the thread can't be spawned, this waits
+    /// for the outputs before returning. Backends which compute synchronously (e.g. CPU) have
+    /// finished by then anyway.
+    ///
+    /// ```no_run
+    /// # use mnn::*;
+    /// # async fn run() -> Result<()> {
+    /// let mut net = Interpreter::from_file("model.mnn")?;
+    /// let session = net.create_session(ScheduleConfig::new())?;
+    /// let pending = net.run_session_async(&session);
+    /// // Prepare the next frame here
+    /// pending.await?;
+    /// let output = net.output::<f32>(&session, "output")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_session_async<'s>(&self, session: &'s Session) -> SessionFuture<'s> {
+        if let Err(e) = self.run_session(session) {
+            return SessionFuture::ready(Err(e));
+        }
+        let off_thread = self.backends(session).is_ok_and(|backends| {
+            backends.iter().any(|&backend| can_wait_off_thread(backend))
+                && backends.iter().all(|&backend| {
+                    backend == crate::ForwardType::CPU || can_wait_off_thread(backend)
+                })
+        });
+        if !off_thread {
+            self.wait(session);
+            return SessionFuture::ready(Ok(()));
+        }
+        let pending = Arc::new(Pending::default());
+        let job = Job {
+            outputs: Outputs(
+                self.outputs(session)
+                    .iter()
+                    .map(|output| output.raw_tensor().inner)
+                    .collect(),
+            ),
+            runtime: session.__runtime.clone(),
+            pending: Arc::clone(&pending),
+        };
+        // Shared with the thread so the job isn't dropped (and finished) if it can't be spawned
+        let job = Arc::new(Mutex::new(Some(job)));
+        let spawned = std::thread::Builder::new()
+            .name("mnn-session-wait".into())
+            .spawn({
+                let job = Arc::clone(&job);
+                move || {
+                    if let Some(job) = Job::take(&job) {
+                        job.wait();
+                    }
+                }
+            });
+        if spawned.is_err() {
+            // Wait here instead, dropping the job finishes it
+            if let Some(job) = Job::take(&job) {
+                job.wait();
+            }
+        }
+        *session.pending.borrow_mut() = Some(Arc::clone(&pending));
+        SessionFuture {
+            state: State::Waiting(pending),
+            __marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    AsTensorShape, Host, HostTensorType, Interpreter, RawTensor, Session, Tensor, TensorShape,
+    prelude::*,
+};
+use std::collections::HashMap;
+
+/// A named input for [`Interpreter::infer`]
+///
+/// Borrows the host data, which is copied into the input tensor of the session before running.
+#[derive(Debug, Clone)]
+pub struct InferInput<'i> {
+    bytes: &'i [u8],
+    len: usize,
+    halide_type: mnn_sys::halide_type_t,
+    type_name: &'static str,
+    shape: Option<TensorShape>,
+}
+
+impl<'i> InferInput<'i> {
+    /// Create an input from a slice, the length must match the current shape of the input tensor
+    pub fn new<H: HalideType>(data: &'i [H]) -> Self {
+        Self {
+            // SAFETY: Halide types are plain numbers without padding
+            bytes: unsafe {
+                core::slice::from_raw_parts(data.as_ptr().cast(), core::mem::size_of_val(data))
+            },
+            len: data.len(),
+            halide_type: mnn_sys::halide_type_of::<H>(),
+            type_name: std::any::type_name::<H>(),
+            shape: None,
+        }
+    }
+
+    /// Create an input from a slice with a shape, the input tensor is resized if its shape differs
+    pub fn with_shape<H: HalideType>(data: &'i [H], shape: impl AsTensorShape) -> Result<Self> {
+        let shape = shape.as_tensor_shape()?;
+        let len = shape
+            .iter()
+            .map(|&dim| dim.max(0) as usize)
+            .product::<usize>();
+        ensure!(
+            len == data.len(),
+            ErrorKind::SizeMismatch {
+                expected: len,
+                got: data.len(),
+            };
+            format!("Data doesn't match the shape {shape:?}")
+        );
+        Ok(Self {
+            shape: Some(shape),
+            ..Self::new(data)
+        })
+    }
+
+    /// Create an input from a host tensor, the input tensor is resized to the shape of `tensor`
+    pub fn from_tensor<T: HostTensorType>(tensor: &'i Tensor<T>) -> Result<Self>
+    where
+        T::H: HalideType,
+    {
+        Self::with_shape(tensor.try_host()?, tensor.shape())
+    }
+}
+
+impl<'i, H: HalideType> From<&'i [H]> for InferInput<'i> {
+    fn from(data: &'i [H]) -> Self {
+        Self::new(data)
+    }
+}
+
+impl<'i, H: HalideType> From<&'i Vec<H>> for InferInput<'i> {
+    fn from(data: &'i Vec<H>) -> Self {
+        Self::new(data)
+    }
+}
+
+/// An output of [`Interpreter::infer`], owns a host copy of the output tensor
+///
+/// The copy is in NCHW (or NHWC for NHWC outputs), the channels of NC4HW4 outputs are unpacked.
+pub struct InferOutput {
+    tensor: RawTensor<'static>,
+}
+
+impl Drop for InferOutput {
+    fn drop(&mut self) {
+        unsafe { mnn_sys::Tensor_destroy(self.tensor.inner) }
+    }
+}
+
+impl core::fmt::Debug for InferOutput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("InferOutput")
+            .field("shape", &self.shape())
+            .field("type", &self.tensor.get_type())
+            .finish()
+    }
+}
+
+impl InferOutput {
+    /// The shape of the output
+    pub fn shape(&self) -> TensorShape {
+        self.tensor.shape()
+    }
+
+    /// Check if the output is of the specified data type
+    pub fn is_type_of<H: HalideType>(&self) -> bool {
+        self.tensor.is_type_of::<H>()
+    }
+
+    /// Get the data of the output
+    pub fn as_slice<H: HalideType>(&self) -> Result<&[H]> {
+        self.ensure_type::<H>()?;
+        Ok(unsafe {
+            core::slice::from_raw_parts(
+                self.tensor.unchecked_host_ptr().cast(),
+                self.tensor.element_size(),
+            )
+        })
+    }
+
+    /// Convert the output into a typed host tensor
+    pub fn into_tensor<H: HalideType>(self) -> Result<Tensor<Host<H>>> {
+        self.ensure_type::<H>()?;
+        let this = core::mem::ManuallyDrop::new(self);
+        Ok(unsafe { Tensor::from_ptr(this.tensor.inner) })
+    }
+
+    fn ensure_type<H: HalideType>(&self) -> Result<()> {
+        ensure!(
+            self.is_type_of::<H>(),
+            ErrorKind::HalideTypeMismatch {
+                got: std::any::type_name::<H>(),
+            };
+            format!("Output is of type {:?}", self.tensor.get_type())
+        );
+        Ok(())
+    }
+}
+
+/// The outputs of [`Interpreter::infer`] by name
+#[derive(Debug, Default)]
+pub struct InferOutputs {
+    outputs: HashMap<String, InferOutput>,
+}
+
+impl InferOutputs {
+    /// Get an output by name
+    pub fn get(&self, name: impl AsRef<str>) -> Result<&InferOutput> {
+        let name = name.as_ref();
+        self.outputs.get(name).ok_or_else(|| {
+            error!(ErrorKind::TensorError).attach_printable(format!("Output \"{name}\" not found"))
+        })
+    }
+
+    /// Get the data of an output by name
+    pub fn slice<H: HalideType>(&self, name: impl AsRef<str>) -> Result<&[H]> {
+        self.get(name)?.as_slice()
+    }
+
+    /// Remove an output and convert it into a typed host tensor
+    pub fn take<H: HalideType>(&mut self, name: impl AsRef<str>) -> Result<Tensor<Host<H>>> {
+        let name = name.as_ref();
+        self.outputs
+            .remove(name)
+            .ok_or_else(|| {
+                error!(ErrorKind::TensorError)
+                    .attach_printable(format!("Output \"{name}\" not found"))
+            })?
+            .into_tensor()
+    }
+
+    /// Names of the outputs
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.outputs.keys().map(String::as_str)
+    }
+
+    /// Number of outputs
+    pub fn len(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Returns true if there are no outputs
+    pub fn is_empty(&self) -> bool {
+        self.outputs.is_empty()
+    }
+}
+
+impl IntoIterator for InferOutputs {
+    type Item = (String, InferOutput);
+    type IntoIter = std::collections::hash_map::IntoIter<String, InferOutput>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.outputs.into_iter()
+    }
+}
+
+impl Interpreter {
+    /// Copy the inputs into the session, run it and copy all outputs back to host memory
+    ///
+    /// Inputs created with a shape (see [`InferInput::with_shape`]) resize the input tensor and
+    /// the session if the shape differs from the current one. The data type and the number of
+    /// elements of every input are checked against the input tensor.
+    ///
+    /// ```no_run
+    /// # use mnn::*;
+    /// # fn main() -> Result<()> {
+    /// let mut net = Interpreter::from_file("model.mnn")?;
+    /// let mut session = net.create_session(ScheduleConfig::new())?;
+    /// let image = vec![0.0f32; 3 * 64 * 64];
+    /// let outputs = net.infer(
+    ///     &mut session,
+    ///     [("input", InferInput::with_shape(&image, [1, 3, 64, 64])?)],
+    /// )?;
+    /// let output: &[f32] = outputs.slice("output")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn infer<'i>(
+        &self,
+        session: &mut Session,
+        inputs: impl IntoIterator<Item = (impl AsRef<str>, InferInput<'i>)>,
+    ) -> Result<InferOutputs> {
+        let inputs = inputs.into_iter().collect::<Vec<_>>();
+
+        let mut resized = false;
+        for (name, input) in &inputs {
+            let name = name.as_ref();
+            let tensor = self.raw_input(session, name)?;
+            ensure!(
+                unsafe { mnn_sys::Tensor_isTypeOf(tensor.inner, input.halide_type) },
+                ErrorKind::HalideTypeMismatch {
+                    got: input.type_name,
+                };
+                format!("Input tensor \"{name}\" is of type {:?}", tensor.get_type())
+            );
+            let current = tensor.shape();
+            if let Some(shape) = input
+                .shape
+                .as_ref()
+                .filter(|shape| shape.as_ref() != current.as_ref())
+            {
+                unsafe {
+                    mnn_sys::Interpreter_resizeTensor(
+                        self.as_ptr(),
+                        tensor.inner,
+                        shape.shape.as_ptr(),
+                        shape.size,
+                    )
+                };
+                resized = true;
+            }
+        }
+        if resized {
+            self.resize_session(session);
+        }
+
+        for (name, input) in &inputs {
+            let name = name.as_ref();
+            let mut tensor = self.raw_input(session, name)?;
+            ensure!(
+                !tensor.is_dynamic_unsized(),
+                ErrorKind::DynamicTensorError;
+                format!("Input tensor \"{name}\" needs a shape")
+            );
+            ensure!(
+                tensor.element_size() == input.len,
+                ErrorKind::SizeMismatch {
+                    expected: tensor.element_size(),
+                    got: input.len,
+                };
+                format!("Input tensor \"{name}\" has shape {:?}", tensor.shape())
+            );
+            // NC4HW4 inputs are converted by MNN while copying
+            let mut host = OwnedHost(tensor.try_create_plain_host_tensor()?);
+            unsafe { host.0.unchecked_host_bytes() }.copy_from_slice(input.bytes);
+            tensor.copy_from_host_tensor(&host.0)?;
+        }
+
+        self.run_session(session)?;
+        self.wait(session);
+
+        let outputs = self
+            .outputs(session)
+            .iter()
+            .map(|output| {
+                let name = output.try_name()?.to_owned();
+                let device = output.raw_tensor();
+                let mut host = OwnedHost(device.try_create_plain_host_tensor()?);
+                device.copy_to_host_tensor(&mut host.0)?;
+                let host = core::mem::ManuallyDrop::new(host);
+                Ok((
+                    name,
+                    InferOutput {
+                        tensor: RawTensor::from_ptr(host.0.inner),
+                    },
+                ))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+        Ok(InferOutputs { outputs })
+    }
+}
+
+/// Destroys the temporary host tensor of an input even if copying fails
+struct OwnedHost(RawTensor<'static>);
+
+impl Drop for OwnedHost {
+    fn drop(&mut self) {
+        unsafe { mnn_sys::Tensor_destroy(self.0.inner) }
+    }
+}
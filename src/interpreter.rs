@@ -7,80 +7,47 @@ use crate::{
 };
 use mnn_sys::HalideType;
 
-pub(crate) type TensorCallbackT = Box<dyn Fn(&[RawTensor], OperatorInfo) -> bool>;
+mod hint;
+mod model_info;
+pub use hint::{GeometryComputeMask, SessionHint};
+pub use model_info::{ModelInfo, TensorDescriptor};
 
-#[repr(transparent)]
-pub(crate) struct TensorCallback {
-    inner: Arc<TensorCallbackT>,
+/// A callback passed to MNN for the duration of a single
+/// [`Interpreter::run_session_with_callback`] call.
+///
+/// The context lives on the stack of the caller, so it's freed as soon as the run returns.
+/// A panic in the callback is stored here and resumed once we are back on the rust side,
+/// since it must not unwind through the C++ frames.
+struct CallbackContext<'c> {
+    callback: &'c mut dyn FnMut(&[RawTensor], OperatorInfo) -> bool,
+    panic: Option<Box<dyn core::any::Any + Send + 'static>>,
 }
 
-impl Default for TensorCallback {
-    fn default() -> Self {
+impl<'c> CallbackContext<'c> {
+    fn new(callback: &'c mut dyn FnMut(&[RawTensor], OperatorInfo) -> bool) -> Self {
         Self {
-            inner: Arc::new(Box::new(|_, _| true)),
+            callback,
+            panic: None,
         }
     }
-}
 
-impl TensorCallback {
-    pub(crate) fn from_ptr(f: *mut libc::c_void) -> Self {
-        debug_assert!(!f.is_null());
-        unsafe {
-            Self {
-                inner: Arc::from_raw(f.cast()),
-            }
-        }
+    fn as_ptr(&mut self) -> *mut libc::c_void {
+        (self as *mut Self).cast()
     }
 
-    pub(crate) fn into_ptr(self) -> *mut libc::c_void {
-        Arc::into_raw(self.inner) as *mut libc::c_void
-    }
-
-    #[cfg(test)]
-    pub(crate) fn identity() -> impl Fn(&[RawTensor], OperatorInfo) -> bool {
-        |_, _| true
-    }
-}
-
-impl<F> From<F> for TensorCallback
-where
-    F: Fn(&[RawTensor], OperatorInfo) -> bool + 'static,
-{
-    fn from(f: F) -> Self {
-        Self {
-            inner: Arc::new(Box::new(f)),
-        }
-    }
-}
-
-impl<T> From<Option<T>> for TensorCallback
-where
-    T: Fn(&[RawTensor], OperatorInfo) -> bool + 'static,
-{
-    fn from(f: Option<T>) -> Self {
-        match f {
-            Some(f) => Self {
-                inner: Arc::new(Box::new(f)),
-            },
-            None => Self::default(),
+    fn resume_panic(self) {
+        if let Some(panic) = self.panic {
+            std::panic::resume_unwind(panic);
         }
     }
 }
 
-impl core::ops::Deref for TensorCallback {
-    type Target = TensorCallbackT;
-
-    fn deref(&self) -> &Self::Target {
-        &self.inner
-    }
-}
-
 /// The session mode to be used
 /// The items are mostly untested and are only documented 1:1 to the C++ codebase
 /// The only two items tested are
 /// - `Debug`
 /// - `Release`
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(windows, repr(i32))]
 #[cfg_attr(unix, repr(u32))]
 pub enum SessionMode {
@@ -91,10 +58,14 @@ pub enum SessionMode {
     #[doc = "About input tensor, Default Session_Input_Inside*/\n/** The input tensor is alloced by session, input data after session resized"]
     InputInside = mnn_sys::SessionMode::Session_Input_Inside,
     #[doc = "The input tensor is alloced by user, set input data before session\nresize"]
+    #[doc = ""]
+    #[doc = "See [`SessionBindings`](crate::SessionBindings) to bind buffers as inputs"]
     InputUser = mnn_sys::SessionMode::Session_Input_User,
     #[doc = "The output tensor depends on session, and can't be separate used"]
     OutputInside = mnn_sys::SessionMode::Session_Output_Inside,
     #[doc = "The output tensor can be separated from session"]
+    #[doc = ""]
+    #[doc = "See [`SessionBindings`](crate::SessionBindings) to bind buffers as outputs"]
     OutputUser = mnn_sys::SessionMode::Session_Output_User,
     #[doc = "Try Resize Session when create Session or not, default direct:"]
     ResizeDirect = mnn_sys::SessionMode::Session_Resize_Direct,
@@ -127,24 +98,62 @@ impl SessionMode {
     fn to_mnn_sys(self) -> SessionModeType {
         self as SessionModeType
     }
+
+    /// Modes of the same group override each other, e.g. `Debug` and `Release`
+    fn group(self) -> u8 {
+        match self {
+            Self::Debug | Self::Release => 0,
+            Self::InputInside | Self::InputUser => 1,
+            Self::OutputInside | Self::OutputUser => 2,
+            Self::ResizeDirect | Self::ResizeDefer => 3,
+            Self::BackendFix | Self::BackendAuto => 4,
+            Self::MemoryCollect | Self::MemoryCache => 5,
+            Self::CodegenDisable | Self::CodegenEnable => 6,
+            Self::ResizeCheck | Self::ResizeFix => 7,
+        }
+    }
 }
 
-/// net data holder. multiple sessions could share same net.
-#[repr(transparent)]
+/// Shared owner of the underlying `MNN::Interpreter`.
+///
+/// Both the [`Interpreter`] and every [`Session`](crate::Session) created from it hold a reference
+/// to this handle, so the net is only destroyed after the last of them is dropped.
 #[derive(Debug)]
-pub struct Interpreter {
-    pub(crate) inner: *mut mnn_sys::Interpreter,
-    pub(crate) __marker: PhantomData<()>,
+pub(crate) struct NetHandle {
+    inner: *mut mnn_sys::Interpreter,
 }
 
-unsafe impl Send for Interpreter {}
+unsafe impl Send for NetHandle {}
+unsafe impl Sync for NetHandle {}
+
+impl NetHandle {
+    pub(crate) fn as_ptr(&self) -> *mut mnn_sys::Interpreter {
+        self.inner
+    }
+}
 
-impl Drop for Interpreter {
+impl Drop for NetHandle {
     fn drop(&mut self) {
         unsafe { mnn_sys::Interpreter_destroy(self.inner) }
     }
 }
 
+/// net data holder. multiple sessions could share same net.
+///
+/// Sessions keep the underlying net alive so the interpreter can safely be dropped before the
+/// sessions created from it.
+#[derive(Debug)]
+pub struct Interpreter {
+    pub(crate) net: Arc<NetHandle>,
+    /// The last session mode set on the interpreter for every group
+    pub(crate) session_modes: Vec<SessionMode>,
+    /// The last session hint set on the interpreter for every hint mode
+    pub(crate) session_hints: Vec<SessionHint>,
+    pub(crate) __marker: PhantomData<()>,
+}
+
+unsafe impl Send for Interpreter {}
+
 impl Interpreter {
     /// Create an net/interpreter from a file.
     ///
@@ -158,10 +167,7 @@ impl Interpreter {
         let c_path = std::ffi::CString::new(path).change_context(ErrorKind::AsciiError)?;
         let interpreter = unsafe { mnn_sys::Interpreter_createFromFile(c_path.as_ptr()) };
         ensure!(!interpreter.is_null(), ErrorKind::InterpreterError; "Failed to create interpreter", "Interpreter_createFromFile returned null");
-        Ok(Self {
-            inner: interpreter,
-            __marker: PhantomData,
-        })
+        Ok(Self::from_raw(interpreter))
     }
 
     /// Create an net/interpreter from a buffer.
@@ -174,11 +180,67 @@ impl Interpreter {
         let size = bytes.len();
         let interpreter =
             unsafe { mnn_sys::Interpreter_createFromBuffer(bytes.as_ptr().cast(), size) };
-        ensure!(!interpreter.is_null(), ErrorKind::InterpreterError; "Failed to create interpreter", "Interpreter_createFromBuffer returned null");
-        Ok(Self {
-            inner: interpreter,
+        ensure!(!interpreter.is_null(), ErrorKind::InterpreterError; "Failed to create interpreter", format!("Model buffer of {size} bytes is not a valid model"), "Interpreter_createFromBuffer returned null");
+        Ok(Self::from_raw(interpreter))
+    }
+
+    /// Create an net/interpreter from a model file whose weights are stored in a separate file.
+    ///
+    /// `path`: the file path of the model
+    ///
+    /// `external`: the file path of the external weights
+    ///
+    /// return: the created net/interpreter
+    pub fn from_file_with_external(
+        path: impl AsRef<Path>,
+        external: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let mut interpreter = Self::from_file(path)?;
+        interpreter.set_external_file(external)?;
+        Ok(interpreter)
+    }
+
+    fn from_raw(interpreter: *mut mnn_sys::Interpreter) -> Self {
+        debug_assert!(!interpreter.is_null());
+        Self {
+            net: Arc::new(NetHandle { inner: interpreter }),
+            session_modes: Vec::new(),
+            session_hints: Vec::new(),
             __marker: PhantomData,
-        })
+        }
+    }
+
+    /// The underlying `MNN::Interpreter`, owned by the net handle shared with the sessions
+    pub(crate) fn as_ptr(&self) -> *mut mnn_sys::Interpreter {
+        self.net.as_ptr()
+    }
+
+    /// Returns true if `session` was created from this interpreter
+    pub fn owns(&self, session: &crate::Session) -> bool {
+        Arc::ptr_eq(&self.net, &session.net)
+    }
+
+    /// Returns the number of sessions created from this interpreter that are still alive
+    pub fn session_count(&self) -> usize {
+        Arc::strong_count(&self.net) - 1
+    }
+
+    /// Returns an error with the model and requested backends if the session wasn't created
+    fn ensure_session_created(
+        &self,
+        session: *mut mnn_sys::Session,
+        internals: &crate::SessionInternals,
+        what: &'static str,
+    ) -> Result<()> {
+        ensure!(
+            !session.is_null(),
+            ErrorKind::InterpreterError;
+            "Failed to create session",
+            format!("Model: {}", self.biz_code().unwrap_or("<unknown>")),
+            format!("Requested backends: {:?}", internals.requested_types()),
+            what
+        );
+        Ok(())
     }
 
     /// Set session mode
@@ -188,7 +250,54 @@ impl Interpreter {
     /// **Warning:**
     /// It should be called before create session!
     pub fn set_session_mode(&mut self, mode: SessionMode) {
-        unsafe { mnn_sys::Interpreter_setSessionMode(self.inner, mode.to_mnn_sys()) }
+        self.session_modes.retain(|set| set.group() != mode.group());
+        self.session_modes.push(mode);
+        unsafe { mnn_sys::Interpreter_setSessionMode(self.as_ptr(), mode.to_mnn_sys()) }
+    }
+
+    /// Set a hint for the sessions created from this interpreter
+    ///
+    /// Hints only apply to sessions created afterwards, sessions which are already alive keep the
+    /// hints they were created with.
+    ///
+    /// Returns [`ErrorKind::InvalidSessionHint`] if
+    /// - the value of the hint is out of range
+    /// - the hint conflicts with the session modes that are set, e.g. a
+    ///   [`GeometryComputeMask`](crate::GeometryComputeMask) without
+    ///   [`OPEN_CACHE`](crate::GeometryComputeMask::OPEN_CACHE) together with
+    ///   [`SessionMode::ResizeCheck`] or [`SessionMode::ResizeFix`]
+    pub fn set_session_hint(&mut self, hint: SessionHint) -> Result<()> {
+        hint.validate()?;
+        let mut hints = self.session_hints.clone();
+        hints.retain(|set| set.mode() != hint.mode());
+        hints.push(hint.clone());
+        Self::validate_session_settings(&self.session_modes, &hints)?;
+        hint.apply(self.as_ptr());
+        self.session_hints = hints;
+        Ok(())
+    }
+
+    /// Check that the session modes and hints don't conflict with each other
+    ///
+    /// Modes can be set after the hints, so this is checked again when a session is created.
+    fn validate_session_settings(modes: &[SessionMode], hints: &[SessionHint]) -> Result<()> {
+        let resize_mode = modes
+            .iter()
+            .find(|mode| matches!(mode, SessionMode::ResizeCheck | SessionMode::ResizeFix));
+        let geometry_mask = hints.iter().find_map(|hint| match hint {
+            SessionHint::GeometryComputeMask(mask) => Some(*mask),
+            _ => None,
+        });
+        if let (Some(mode), Some(mask)) = (resize_mode, geometry_mask) {
+            // The resize check compares against the cached geometry of the last resize
+            ensure!(
+                mask.contains(GeometryComputeMask::OPEN_CACHE),
+                ErrorKind::InvalidSessionHint;
+                format!("SessionMode::{mode:?} requires GeometryComputeMask::OPEN_CACHE"),
+                format!("GeometryComputeMask: {:#x}", mask.bits())
+            );
+        }
+        Ok(())
     }
 
     ///call this function to get tensors ready.
@@ -197,7 +306,9 @@ impl Interpreter {
     ///
     ///`session`: the session to be prepared
     pub fn resize_session(&self, session: &mut crate::Session) {
-        unsafe { mnn_sys::Interpreter_resizeSession(self.inner, session.inner) }
+        session.wait_pending();
+        let _guard = session.lock_runtime();
+        unsafe { mnn_sys::Interpreter_resizeSession(self.as_ptr(), session.inner) }
     }
 
     /// Resize session and reallocate the buffer.
@@ -207,21 +318,31 @@ impl Interpreter {
     /// # Note
     /// NeedRelloc is default to 1, 1 means need realloc!
     pub fn resize_session_reallocate(&self, session: &mut crate::Session) {
-        unsafe { mnn_sys::Interpreter_resizeSessionWithFlag(self.inner, session.inner, 1i32) }
+        session.wait_pending();
+        let _guard = session.lock_runtime();
+        unsafe { mnn_sys::Interpreter_resizeSessionWithFlag(self.as_ptr(), session.inner, 1i32) }
     }
 
     /// Resize the tensor using the given shape
-    pub fn resize_tensor<T: TensorType>(&self, tensor: &mut Tensor<T>, dims: impl AsTensorShape) {
-        let dims = dims.as_tensor_shape();
+    ///
+    /// Returns an error if the shape has more than [`MAX_TENSOR_DIMS`](crate::MAX_TENSOR_DIMS)
+    /// dimensions
+    pub fn resize_tensor<T: TensorType>(
+        &self,
+        tensor: &mut Tensor<T>,
+        dims: impl AsTensorShape,
+    ) -> Result<()> {
+        let dims = dims.as_tensor_shape()?;
         let dims_len = dims.size;
         unsafe {
             mnn_sys::Interpreter_resizeTensor(
-                self.inner,
+                self.as_ptr(),
                 tensor.tensor,
                 dims.shape.as_ptr(),
                 dims_len,
             )
         }
+        Ok(())
     }
 
     /// Resize tensor by
@@ -239,7 +360,7 @@ impl Interpreter {
     ) {
         unsafe {
             mnn_sys::Interpreter_resizeTensorByNCHW(
-                self.inner,
+                self.as_ptr(),
                 tensor.tensor,
                 batch.into(),
                 channel.into(),
@@ -258,13 +379,51 @@ impl Interpreter {
         &mut self,
         schedule: crate::ScheduleConfig,
     ) -> Result<crate::session::Session> {
+        schedule.validate()?;
+        Self::validate_session_settings(&self.session_modes, &self.session_hints)?;
         profile!("Creating session"; {
-            let session = unsafe { mnn_sys::Interpreter_createSession(self.inner, schedule.inner) };
-            assert!(!session.is_null());
+            let session = unsafe { mnn_sys::Interpreter_createSession(self.as_ptr(), schedule.inner) };
+            let internals = crate::SessionInternals::Single(schedule);
+            self.ensure_session_created(session, &internals, "Interpreter_createSession returned null")?;
+            Ok(crate::session::Session {
+                inner: session,
+                net: Arc::clone(&self.net),
+                modes: self.session_modes.clone(),
+                __runtime: None,
+                __session_internals: internals,
+                pending: Default::default(),
+                __marker: PhantomData,
+            })
+        })
+    }
+
+    /// Create a session with session config using a shared runtime. Session will be managed in net/interpreter.
+    ///
+    /// `schedule` : the config of the session
+    ///
+    /// `runtime` : the runtime to create the session with, it is kept alive by the session
+    ///
+    /// return: the created session
+    pub fn create_session_with_runtime(
+        &mut self,
+        schedule: crate::ScheduleConfig,
+        runtime: &Arc<crate::Runtime>,
+    ) -> Result<crate::session::Session> {
+        schedule.validate()?;
+        Self::validate_session_settings(&self.session_modes, &self.session_hints)?;
+        profile!("Creating session with runtime"; {
+            let guard = runtime.lock();
+            let session = unsafe { mnn_sys::Interpreter_createSessionWithRuntime(self.as_ptr(), schedule.inner, runtime.inner) };
+            let internals = crate::SessionInternals::Single(schedule);
+            drop(guard);
+            self.ensure_session_created(session, &internals, "Interpreter_createSessionWithRuntime returned null")?;
             Ok(crate::session::Session {
                 inner: session,
-                net: self.inner,
-                __session_internals: crate::SessionInternals::Single(schedule),
+                net: Arc::clone(&self.net),
+                modes: self.session_modes.clone(),
+                __runtime: Some(Arc::clone(runtime)),
+                __session_internals: internals,
+                pending: Default::default(),
                 __marker: PhantomData,
             })
         })
@@ -275,7 +434,7 @@ impl Interpreter {
     /// This function is marked unsafe since it's not clear what the safety guarantees are right
     /// now. With a simple test it caused a segfault so it's marked unsafe
     pub unsafe fn release_model(&mut self) {
-        unsafe { mnn_sys::Interpreter_releaseModel(self.inner) }
+        unsafe { mnn_sys::Interpreter_releaseModel(self.as_ptr()) }
     }
 
     /// Create multi-path session with schedule configs and user-specified runtime. created session will be managed in net/interpreter.
@@ -287,15 +446,59 @@ impl Interpreter {
         &mut self,
         schedule: impl IntoIterator<Item = ScheduleConfig>,
     ) -> Result<crate::session::Session> {
+        Self::validate_session_settings(&self.session_modes, &self.session_hints)?;
         profile!("Creating multipath session"; {
-            let schedules: crate::ScheduleConfigs = schedule.into_iter().collect();
+            let schedules: crate::ScheduleConfigs = schedule
+                .into_iter()
+                .map(|config| config.validate().map(|_| config))
+                .collect::<Result<_>>()?;
             let sc: &[_] = schedules.inner.as_ref();
-            let session = unsafe { mnn_sys::Interpreter_createMultiPathSession(self.inner, sc.as_ptr(), sc.len()) };
-            assert!(!session.is_null());
+            let session = unsafe { mnn_sys::Interpreter_createMultiPathSession(self.as_ptr(), sc.as_ptr(), sc.len()) };
+            let internals = crate::SessionInternals::MultiSession(schedules);
+            self.ensure_session_created(session, &internals, "Interpreter_createMultiPathSession returned null")?;
             Ok(crate::session::Session {
                 inner: session,
-                net: self.inner,
-                __session_internals: crate::SessionInternals::MultiSession(schedules),
+                net: Arc::clone(&self.net),
+                modes: self.session_modes.clone(),
+                __runtime: None,
+                __session_internals: internals,
+                pending: Default::default(),
+                __marker: PhantomData,
+            })
+        })
+    }
+
+    /// Create multi-path session with schedule configs using a shared runtime. created session will be managed in net/interpreter.
+    ///
+    /// `schedule` : the config of the session
+    ///
+    /// `runtime` : the runtime to create the session with, it is kept alive by the session
+    ///
+    /// return: the created session
+    pub fn create_multipath_session_with_runtime(
+        &mut self,
+        schedule: impl IntoIterator<Item = ScheduleConfig>,
+        runtime: &Arc<crate::Runtime>,
+    ) -> Result<crate::session::Session> {
+        Self::validate_session_settings(&self.session_modes, &self.session_hints)?;
+        profile!("Creating multipath session with runtime"; {
+            let schedules: crate::ScheduleConfigs = schedule
+                .into_iter()
+                .map(|config| config.validate().map(|_| config))
+                .collect::<Result<_>>()?;
+            let sc: &[_] = schedules.inner.as_ref();
+            let guard = runtime.lock();
+            let session = unsafe { mnn_sys::Interpreter_createMultiPathSessionWithRuntime(self.as_ptr(), sc.as_ptr(), sc.len(), runtime.inner) };
+            let internals = crate::SessionInternals::MultiSession(schedules);
+            drop(guard);
+            self.ensure_session_created(session, &internals, "Interpreter_createMultiPathSessionWithRuntime returned null")?;
+            Ok(crate::session::Session {
+                inner: session,
+                net: Arc::clone(&self.net),
+                modes: self.session_modes.clone(),
+                __runtime: Some(Arc::clone(runtime)),
+                __session_internals: internals,
+                pending: Default::default(),
                 __marker: PhantomData,
             })
         })
@@ -311,13 +514,66 @@ impl Interpreter {
         Ok(())
     }
 
+    /// Get the inputs, outputs and metadata of the model
+    ///
+    /// This only reads the model buffer so it doesn't create a session or allocate any backend
+    /// memory.
+    ///
+    /// Fails if the model was released with [`Interpreter::release_model`]
+    pub fn model_info(&self) -> Result<ModelInfo> {
+        let (inputs, outputs) = model_info::TensorDescriptorArray::describe(self.as_ptr())?;
+        Ok(ModelInfo {
+            inputs: inputs.to_vec(),
+            outputs: outputs.to_vec(),
+            version: self.model_version()?.to_owned(),
+            biz_code: self.biz_code().map(ToOwned::to_owned),
+            uuid: self.uuid().map(ToOwned::to_owned),
+        })
+    }
+
+    /// Get the version of MNN the model was converted with
+    pub fn model_version(&self) -> Result<&str> {
+        let version = unsafe { mnn_sys::Interpreter_getModelVersion(self.as_ptr()) };
+        ensure!(!version.is_null(), ErrorKind::InterpreterError; "Failed to get model version");
+        unsafe { CStr::from_ptr(version) }
+            .to_str()
+            .change_context(ErrorKind::AsciiError)
+            .map_err(Into::into)
+    }
+
+    /// Get the business code of the model
+    ///
+    /// Returns `None` if the model doesn't have one (or it is not valid utf-8)
+    pub fn biz_code(&self) -> Option<&str> {
+        let biz_code = unsafe { mnn_sys::Interpreter_bizCode(self.as_ptr()) };
+        if biz_code.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(biz_code) }.to_str().ok()
+    }
+
+    /// Get the uuid of the model
+    ///
+    /// Returns `None` if the model doesn't have one (or it is not valid utf-8)
+    pub fn uuid(&self) -> Option<&str> {
+        let uuid = unsafe { mnn_sys::Interpreter_uuid(self.as_ptr()) };
+        if uuid.is_null() {
+            return None;
+        }
+        unsafe { CStr::from_ptr(uuid) }
+            .to_str()
+            .ok()
+            .filter(|uuid| !uuid.is_empty())
+    }
+
     /// Get the input tensor of the session.
     ///
     /// `session`: the session to get input tensor
     ///
     /// return: List of input tensors
     pub fn inputs<'i>(&self, session: &'i crate::Session) -> TensorList<'i> {
-        let inputs = unsafe { mnn_sys::Interpreter_getSessionInputAll(self.inner, session.inner) };
+        let inputs =
+            unsafe { mnn_sys::Interpreter_getSessionInputAll(self.as_ptr(), session.inner) };
         TensorList::from_ptr(inputs)
     }
 
@@ -336,7 +592,7 @@ impl Interpreter {
         let name = name.as_ref();
         let c_name = std::ffi::CString::new(name).change_context(ErrorKind::AsciiError)?;
         let input = unsafe {
-            mnn_sys::Interpreter_getSessionInput(self.inner, session.inner, c_name.as_ptr())
+            mnn_sys::Interpreter_getSessionInput(self.as_ptr(), session.inner, c_name.as_ptr())
         };
         ensure!(!input.is_null(), ErrorKind::TensorError; format!("Input tensor \"{name}\" not found"));
         let tensor = unsafe { Tensor::from_ptr(input) };
@@ -361,7 +617,7 @@ impl Interpreter {
         let name = name.as_ref();
         let c_name = std::ffi::CString::new(name).change_context(ErrorKind::AsciiError)?;
         let input = unsafe {
-            mnn_sys::Interpreter_getSessionInput(self.inner, session.inner, c_name.as_ptr())
+            mnn_sys::Interpreter_getSessionInput(self.as_ptr(), session.inner, c_name.as_ptr())
         };
         ensure!(!input.is_null(), ErrorKind::TensorError; format!("Input tensor \"{name}\" not found"));
         Ok(RawTensor::from_ptr(input))
@@ -377,7 +633,7 @@ impl Interpreter {
         let name = name.as_ref();
         let c_name = std::ffi::CString::new(name).change_context(ErrorKind::AsciiError)?;
         let input = unsafe {
-            mnn_sys::Interpreter_getSessionInput(self.inner, session.inner, c_name.as_ptr())
+            mnn_sys::Interpreter_getSessionInput(self.as_ptr(), session.inner, c_name.as_ptr())
         };
         ensure!(!input.is_null(), ErrorKind::TensorError; format!("Input tensor \"{name}\" not found"));
         let tensor = unsafe { Tensor::from_ptr(input) };
@@ -394,20 +650,20 @@ impl Interpreter {
     /// Very **unsafe** since it doesn't check the type of the tensor
     /// as well as the shape of the tensor
     ///
-    /// **Panics** if the name is not ascii
+    /// Returns an error if the name contains a nul byte or the tensor doesn't exist
     /// **Undefined Behavior** if the tensor is not of type `H`
     pub unsafe fn input_unchecked<'s, H: HalideType>(
         &self,
         session: &'s crate::Session,
         name: impl AsRef<str>,
-    ) -> Tensor<RefMut<'s, Device<H>>> {
+    ) -> Result<Tensor<RefMut<'s, Device<H>>>> {
         let name = name.as_ref();
-        let c_name = std::ffi::CString::new(name).expect("Input tensor name is not ascii");
-        unsafe {
-            let input =
-                mnn_sys::Interpreter_getSessionInput(self.inner, session.inner, c_name.as_ptr());
-            Tensor::from_ptr(input)
-        }
+        let c_name = std::ffi::CString::new(name).change_context(ErrorKind::AsciiError)?;
+        let input = unsafe {
+            mnn_sys::Interpreter_getSessionInput(self.as_ptr(), session.inner, c_name.as_ptr())
+        };
+        ensure!(!input.is_null(), ErrorKind::TensorError; format!("Input tensor \"{name}\" not found"));
+        Ok(unsafe { Tensor::from_ptr(input) })
     }
 
     /// Get the output tensor of a session by name
@@ -420,10 +676,11 @@ impl Interpreter {
         session: &'s crate::Session,
         name: impl AsRef<str>,
     ) -> Result<Tensor<Ref<'s, Device<H>>>> {
+        session.wait_pending();
         let name = name.as_ref();
         let c_name = std::ffi::CString::new(name).change_context(ErrorKind::AsciiError)?;
         let output = unsafe {
-            mnn_sys::Interpreter_getSessionOutput(self.inner, session.inner, c_name.as_ptr())
+            mnn_sys::Interpreter_getSessionOutput(self.as_ptr(), session.inner, c_name.as_ptr())
         };
         ensure!(!output.is_null(), ErrorKind::IOError;format!("Output tensor \"{name}\" not found"));
         let tensor = unsafe { Tensor::from_ptr(output) };
@@ -444,24 +701,26 @@ impl Interpreter {
         session: &'s crate::Session,
         name: impl AsRef<str>,
     ) -> Result<RawTensor<'s>> {
+        session.wait_pending();
         let name = name.as_ref();
         let c_name = std::ffi::CString::new(name).change_context(ErrorKind::AsciiError)?;
         let output = unsafe {
-            mnn_sys::Interpreter_getSessionOutput(self.inner, session.inner, c_name.as_ptr())
+            mnn_sys::Interpreter_getSessionOutput(self.as_ptr(), session.inner, c_name.as_ptr())
         };
         ensure!(!output.is_null(), ErrorKind::IOError;format!("Output tensor \"{name}\" not found"));
         Ok(RawTensor::from_ptr(output))
     }
 
     /// Run a session
-    pub fn run_session(&mut self, session: &crate::session::Session) -> Result<()> {
+    ///
+    /// Different sessions of the same interpreter can be run concurrently, see
+    /// [`SessionPool`](crate::SessionPool)
+    pub fn run_session(&self, session: &crate::session::Session) -> Result<()> {
+        session.wait_pending();
         profile!("Running session"; {
-            let ret = unsafe { mnn_sys::Interpreter_runSession(self.inner, session.inner) };
-            ensure!(
-                ret == mnn_sys::ErrorCode::ERROR_CODE_NO_ERROR,
-                ErrorKind::InternalError(ret)
-            );
-            Ok(())
+            let _guard = session.lock_runtime();
+            let ret = unsafe { mnn_sys::Interpreter_runSession(self.as_ptr(), session.inner) };
+            MNNError::from_error_code(ret)
         })
     }
 
@@ -474,39 +733,223 @@ impl Interpreter {
     /// `after` : a callback after each op. return true to continue running; return false to interrupt the session.
     ///
     /// `sync` : synchronously wait for finish of execution or not.
+    ///
+    /// The callbacks are only called during this function so they can borrow local state.
+    /// If a callback panics the session is interrupted and the panic is resumed after MNN returns.
     pub fn run_session_with_callback(
         &mut self,
         session: &crate::session::Session,
-        before: impl Fn(&[RawTensor], OperatorInfo) -> bool + 'static,
-        end: impl Fn(&[RawTensor], OperatorInfo) -> bool + 'static,
+        mut before: impl FnMut(&[RawTensor], OperatorInfo) -> bool,
+        mut end: impl FnMut(&[RawTensor], OperatorInfo) -> bool,
         sync: bool,
     ) -> Result<()> {
+        session.wait_pending();
         let sync = sync as libc::c_int;
-        let before = TensorCallback::from(before).into_ptr();
-        let end = TensorCallback::from(end).into_ptr();
+        let mut before = CallbackContext::new(&mut before);
+        let mut end = CallbackContext::new(&mut end);
+        let guard = session.lock_runtime();
         let ret = unsafe {
             mnn_sys::Interpreter_runSessionWithCallBackInfo(
-                self.inner,
+                self.as_ptr(),
                 session.inner,
-                before,
-                end,
+                before.as_ptr(),
+                end.as_ptr(),
                 sync,
             )
         };
+        drop(guard);
+        before.resume_panic();
+        end.resume_panic();
+        MNNError::from_error_code(ret)
+    }
+
+    /// Run a session which can be stopped from another thread with a [`CancellationToken`]
+    ///
+    /// The token is checked after every operator. If it was cancelled the session is interrupted
+    /// and [`ErrorKind::Cancelled`] is returned. The session can be run again afterwards, but the
+    /// outputs are only valid after a run that wasn't cancelled.
+    ///
+    /// Cancellation relies on the operator callbacks so this returns an error if the session mode
+    /// is [`SessionMode::Release`].
+    ///
+    /// ```no_run
+    /// # use mnn::*;
+    /// # fn main() -> Result<()> {
+    /// let mut net = Interpreter::from_file("model.mnn")?;
+    /// let session = net.create_session(ScheduleConfig::new())?;
+    /// let token = CancellationToken::new();
+    /// let cancel = token.clone();
+    /// std::thread::spawn(move || cancel.cancel());
+    /// if let Err(e) = net.run_session_with_cancellation(&session, &token) {
+    ///     println!("Session stopped: {e}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_session_with_cancellation(
+        &mut self,
+        session: &crate::session::Session,
+        token: &crate::CancellationToken,
+    ) -> Result<()> {
+        self.run_session_with_callback_and_cancellation(session, |_, _| true, |_, _| true, token)
+    }
+
+    /// Run a session with operator callbacks which can be stopped with a [`CancellationToken`]
+    ///
+    /// The token is checked after the `end` callback of every operator, so cancelling it from
+    /// inside a callback stops the session after that operator. Returning `false` from the `end`
+    /// callback stops the session with [`ErrorKind::CallbackStopped`], see
+    /// [`Interpreter::run_session_with_callback`].
+    pub fn run_session_with_callback_and_cancellation(
+        &mut self,
+        session: &crate::session::Session,
+        before: impl FnMut(&[RawTensor], OperatorInfo) -> bool,
+        mut end: impl FnMut(&[RawTensor], OperatorInfo) -> bool,
+        token: &crate::CancellationToken,
+    ) -> Result<()> {
         ensure!(
-            ret == mnn_sys::ErrorCode::ERROR_CODE_NO_ERROR,
-            ErrorKind::InternalError(ret)
+            !session.has_mode(SessionMode::Release),
+            ErrorKind::InterpreterError;
+            "Cancellation needs operator callbacks which are disabled with SessionMode::Release"
         );
-        Ok(())
+        ensure!(!token.is_cancelled(), ErrorKind::Cancelled);
+        let mut cancelled = false;
+        let ret = self.run_session_with_callback(
+            session,
+            before,
+            |tensors, op| {
+                let proceed = end(tensors, op);
+                cancelled = token.is_cancelled();
+                proceed && !cancelled
+            },
+            true,
+        );
+        ensure!(!cancelled, ErrorKind::Cancelled);
+        ret
+    }
+
+    /// Run a session and copy the named tensors to host memory as soon as they are computed.
+    ///
+    /// Intermediate tensors are only kept by MNN when they are listed in
+    /// [`ScheduleConfig::set_save_tensors`] of the config the session was created with, otherwise
+    /// an error is returned for the missing tensor. Model inputs and outputs can always be captured.
+    ///
+    /// The copies are made in the after callback of the operator producing each tensor, so the
+    /// session mode must not be [`SessionMode::Release`]. Tensors which are not produced by any
+    /// operator (for example the inputs) are copied after the session has finished.
+    ///
+    /// ```no_run
+    /// # use mnn::*;
+    /// # fn main() -> Result<()> {
+    /// let mut net = Interpreter::from_file("model.mnn")?;
+    /// let mut config = ScheduleConfig::new();
+    /// config.set_save_tensors(&["conv1"])?;
+    /// let session = net.create_session(config)?;
+    /// let captured = net.run_session_capturing::<f32>(&session, ["conv1"])?;
+    /// println!("{:?}", captured["conv1"].host());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_session_capturing<H: HalideType>(
+        &mut self,
+        session: &crate::session::Session,
+        names: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<std::collections::HashMap<String, Tensor<crate::Host<H>>>> {
+        let mut targets = Vec::new();
+        for name in names {
+            let name = name.as_ref();
+            let c_name = std::ffi::CString::new(name).change_context(ErrorKind::AsciiError)?;
+            let tensor = unsafe {
+                mnn_sys::Interpreter_getSessionOutput(self.as_ptr(), session.inner, c_name.as_ptr())
+            };
+            ensure!(
+                !tensor.is_null(),
+                ErrorKind::TensorError;
+                format!("Tensor \"{name}\" not found"),
+                "Intermediate tensors need to be added to ScheduleConfig::set_save_tensors"
+            );
+            ensure!(
+                unsafe { Tensor::<Ref<'_, Device<H>>>::from_ptr(tensor) }.is_type_of::<H>(),
+                ErrorKind::HalideTypeMismatch {
+                    got: std::any::type_name::<H>(),
+                };
+                format!("Tensor \"{name}\" is not of type {}", std::any::type_name::<H>())
+            );
+            targets.push((name.to_owned(), tensor));
+        }
+
+        let mut captured = std::collections::HashMap::<String, Tensor<crate::Host<H>>>::new();
+        let mut copy_error = None;
+        let ret = self.run_session_with_callback(
+            session,
+            |_, _| true,
+            |tensors, _| {
+                for tensor in tensors {
+                    for (name, _) in targets.iter().filter(|(_, ptr)| *ptr == tensor.inner) {
+                        match tensor.try_create_host_tensor_from_device(true) {
+                            Ok(host) => {
+                                captured.insert(name.clone(), unsafe { host.to_concrete() });
+                            }
+                            Err(e) => {
+                                copy_error = Some(e.attach_printable(format!(
+                                    "Failed to capture tensor \"{name}\""
+                                )));
+                                return false;
+                            }
+                        }
+                    }
+                }
+                true
+            },
+            true,
+        );
+        if let Some(e) = copy_error {
+            return Err(e);
+        }
+        ret?;
+
+        for (name, tensor) in targets {
+            if !captured.contains_key(&name) {
+                let host = RawTensor::from_ptr(tensor)
+                    .try_create_host_tensor_from_device(true)
+                    .map_err(|e| {
+                        e.attach_printable(format!("Failed to capture tensor \"{name}\""))
+                    })?;
+                captured.insert(name, unsafe { host.to_concrete() });
+            }
+        }
+        Ok(captured)
     }
 
     /// Get all output tensors of a session
     pub fn outputs<'o>(&self, session: &'o crate::session::Session) -> TensorList<'o> {
+        session.wait_pending();
         let outputs =
-            unsafe { mnn_sys::Interpreter_getSessionOutputAll(self.inner, session.inner) };
+            unsafe { mnn_sys::Interpreter_getSessionOutputAll(self.as_ptr(), session.inner) };
         TensorList::from_ptr(outputs)
     }
 
+    /// Set the file containing the external weights of the model.
+    ///
+    /// `path` : the file path of the external weights
+    ///
+    /// # Note
+    /// The API should be called before create session.
+    pub fn set_external_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        ensure!(path.exists(), ErrorKind::IOError; path.to_string_lossy().to_string(), "File not found");
+        let path = path.to_str().ok_or_else(|| error!(ErrorKind::AsciiError))?;
+        let c_path = std::ffi::CString::new(path).change_context(ErrorKind::AsciiError)?;
+        unsafe {
+            mnn_sys::Interpreter_setExternalFile(
+                self.as_ptr(),
+                c_path.as_ptr(),
+                mnn_sys::EXTERNAL_FILE_FLAG_DEFAULT as usize,
+            )
+        }
+        Ok(())
+    }
+
     /// If the cache exist, try to load cache from file.
     /// After createSession, try to save cache to file.
     ///
@@ -516,6 +959,7 @@ impl Interpreter {
     ///
     /// # Note
     /// The API should be called before create session.
+    /// See [`Interpreter::create_session_with_cache`] to keep the cache in memory instead.
     ///
     /// Key Depercerate, keeping for future use!
     pub fn set_cache_file(&mut self, path: impl AsRef<Path>, key_size: usize) -> Result<()> {
@@ -523,16 +967,16 @@ impl Interpreter {
         let path = dunce::simplified(path);
         let path = path.to_str().ok_or_else(|| error!(ErrorKind::AsciiError))?;
         let c_path = std::ffi::CString::new(path).change_context(ErrorKind::AsciiError)?;
-        unsafe { mnn_sys::Interpreter_setCacheFile(self.inner, c_path.as_ptr(), key_size) }
+        unsafe { mnn_sys::Interpreter_setCacheFile(self.as_ptr(), c_path.as_ptr(), key_size) }
         Ok(())
     }
 
     /// Update cache file
     pub fn update_cache_file(&mut self, session: &mut crate::session::Session) -> Result<()> {
+        let _guard = session.lock_runtime();
         MNNError::from_error_code(unsafe {
-            mnn_sys::Interpreter_updateCacheFile(self.inner, session.inner)
-        });
-        Ok(())
+            mnn_sys::Interpreter_updateCacheFile(self.as_ptr(), session.inner)
+        })
     }
 
     /// Wait for all output tensors to be ready after computation
@@ -544,63 +988,58 @@ impl Interpreter {
         });
     }
 
-    /// Get memory usage of a session in MB
-    pub fn memory(&self, session: &crate::session::Session) -> Result<f32> {
-        let mut memory = 0f32;
-        let memory_ptr = &mut memory as *mut f32;
+    /// Query the session info for `code` and write it to `ptr`
+    fn session_info_raw(
+        &self,
+        session: &crate::Session,
+        code: mnn_sys::cpp::MNN_Interpreter_SessionInfoCode,
+        ptr: *mut libc::c_void,
+        what: &'static str,
+    ) -> Result<()> {
         let ret = unsafe {
-            mnn_sys::Interpreter_getSessionInfo(
-                self.inner,
-                session.inner,
-                mnn_sys::cpp::MNN_Interpreter_SessionInfoCode_MEMORY as _,
-                memory_ptr.cast(),
-            )
+            mnn_sys::Interpreter_getSessionInfo(self.as_ptr(), session.inner, code as _, ptr)
         };
         ensure!(
             ret == 1,
             ErrorKind::InterpreterError;
-            "Failed to get memory usage"
+            format!("Failed to get {what}")
         );
+        Ok(())
+    }
+
+    /// Get memory usage of a session in MB
+    pub fn memory(&self, session: &crate::session::Session) -> Result<f32> {
+        let mut memory = 0f32;
+        self.session_info_raw(
+            session,
+            mnn_sys::cpp::MNN_Interpreter_SessionInfoCode_MEMORY,
+            (&mut memory as *mut f32).cast(),
+            "memory usage",
+        )?;
         Ok(memory)
     }
 
     /// Get float operation needed in session in M
     pub fn flops(&self, session: &crate::Session) -> Result<f32> {
         let mut flop = 0.0f32;
-        let flop_ptr = &mut flop as *mut f32;
-        let ret = unsafe {
-            mnn_sys::Interpreter_getSessionInfo(
-                self.inner,
-                session.inner,
-                mnn_sys::cpp::MNN_Interpreter_SessionInfoCode_FLOPS as _,
-                flop_ptr.cast::<libc::c_void>(),
-            )
-        };
-        ensure!(
-            ret == 1,
-            ErrorKind::InterpreterError;
-            "Failed to get flops"
-        );
+        self.session_info_raw(
+            session,
+            mnn_sys::cpp::MNN_Interpreter_SessionInfoCode_FLOPS,
+            (&mut flop as *mut f32).cast(),
+            "flops",
+        )?;
         Ok(flop)
     }
 
     /// Get the resize status
     pub fn resize_status(&self, session: &crate::Session) -> Result<ResizeStatus> {
         let mut resize_status = 0i32;
-        let ptr = &mut resize_status as *mut i32;
-        let ret = unsafe {
-            mnn_sys::Interpreter_getSessionInfo(
-                self.inner,
-                session.inner,
-                mnn_sys::cpp::MNN_Interpreter_SessionInfoCode_RESIZE_STATUS as _,
-                ptr.cast(),
-            )
-        };
-        ensure!(
-        ret == 1,
-            ErrorKind::InterpreterError;
-            "Failed to get resize status"
-        );
+        self.session_info_raw(
+            session,
+            mnn_sys::cpp::MNN_Interpreter_SessionInfoCode_RESIZE_STATUS,
+            (&mut resize_status as *mut i32).cast(),
+            "resize status",
+        )?;
         match resize_status {
             0 => Ok(ResizeStatus::None),
             1 => Ok(ResizeStatus::NeedMalloc),
@@ -608,10 +1047,121 @@ impl Interpreter {
             _ => Err(error!(ErrorKind::InterpreterError)),
         }
     }
+
+    /// Get the backends of the runtimes that were actually created for the session
+    ///
+    /// MNN returns one entry for each runtime of the session, this includes the backup CPU
+    /// runtime. Returns an error if MNN reports a backend that isn't enabled in this build.
+    pub fn backends(&self, session: &crate::Session) -> Result<Vec<crate::ForwardType>> {
+        self.raw_backends(session)?
+            .into_iter()
+            .map(crate::ForwardType::try_from_raw)
+            .collect()
+    }
+
+    /// The raw `MNNForwardType` values of the runtimes of the session
+    fn raw_backends(&self, session: &crate::Session) -> Result<Vec<i32>> {
+        // One entry per schedule config and the backup CPU runtime, unless the session was created
+        // with a runtime which has more backends
+        let mut backends = vec![0i32; session.__session_internals.requested_types().len() + 1];
+        loop {
+            let count = unsafe {
+                mnn_sys::Interpreter_getSessionBackends(
+                    self.as_ptr(),
+                    session.inner,
+                    backends.as_mut_ptr(),
+                    backends.len(),
+                )
+            };
+            ensure!(count >= 0, ErrorKind::InterpreterError; "Failed to get backends");
+            let count = count as usize;
+            if count <= backends.len() {
+                backends.truncate(count);
+                return Ok(backends);
+            }
+            backends.resize(count, 0);
+        }
+    }
+
+    /// Get the number of threads used by the session
+    pub fn thread_number(&self, session: &crate::Session) -> Result<u32> {
+        let mut threads = 0i32;
+        self.session_info_raw(
+            session,
+            mnn_sys::cpp::MNN_Interpreter_SessionInfoCode_THREAD_NUMBER,
+            (&mut threads as *mut i32).cast(),
+            "thread number",
+        )?;
+        Ok(threads.max(0) as u32)
+    }
+
+    /// Get the backend a session tensor is allocated on
+    ///
+    /// Useful to check whether a tensor fell back to CPU when using [`ForwardType::Auto`](crate::ForwardType::Auto)
+    /// or a backup type. Returns an error if the tensor doesn't belong to the session or MNN
+    /// reports a backend this build doesn't know about.
+    pub fn tensor_backend<T: TensorType>(
+        &self,
+        session: &crate::Session,
+        tensor: &Tensor<T>,
+    ) -> Result<crate::ForwardType> {
+        self.backend_of(session, tensor.tensor)
+    }
+
+    /// Get the backend a session tensor is allocated on, see [`Interpreter::tensor_backend`]
+    pub fn raw_tensor_backend(
+        &self,
+        session: &crate::Session,
+        tensor: &RawTensor,
+    ) -> Result<crate::ForwardType> {
+        self.backend_of(session, tensor.inner)
+    }
+
+    fn backend_of(
+        &self,
+        session: &crate::Session,
+        tensor: *const mnn_sys::Tensor,
+    ) -> Result<crate::ForwardType> {
+        let backend =
+            unsafe { mnn_sys::Interpreter_getBackend(self.as_ptr(), session.inner, tensor) };
+        let forward_type = unsafe { mnn_sys::Backend_getType(backend) };
+        ensure!(
+            forward_type >= 0,
+            ErrorKind::InterpreterError;
+            "Tensor doesn't have a backend",
+            "The tensor might not belong to this session"
+        );
+        crate::ForwardType::try_from_raw(forward_type)
+    }
+
+    /// Get a snapshot of what MNN actually scheduled for the session
+    ///
+    /// Unlike [`Interpreter::backends`] backends which aren't enabled in this build don't fail the
+    /// snapshot, they are reported in [`SessionInfo::unknown_backends`](crate::SessionInfo::unknown_backends).
+    pub fn session_info(&self, session: &crate::Session) -> Result<crate::SessionInfo> {
+        let mut backends = Vec::new();
+        let mut unknown_backends = Vec::new();
+        for raw in self.raw_backends(session)? {
+            match crate::ForwardType::from_raw(raw) {
+                Some(backend) => backends.push(backend),
+                None => unknown_backends.push(raw),
+            }
+        }
+        Ok(crate::SessionInfo {
+            memory: self.memory(session)?,
+            flops: self.flops(session)?,
+            backends,
+            unknown_backends,
+            requested: session.__session_internals.requested_types(),
+            thread_number: self.thread_number(session)?,
+            resize_status: self.resize_status(session)?,
+        })
+    }
 }
 
 /// The status of the resize operation
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub enum ResizeStatus {
     /// No resize needed
@@ -628,23 +1178,35 @@ extern "C" fn rust_closure_callback_runner_op(
     tensors: *const *mut mnn_sys::Tensor,
     tensor_count: usize,
     op: *mut libc::c_void,
+    forward_type: libc::c_int,
 ) -> libc::c_int {
+    debug_assert!(!f.is_null());
+    let context = unsafe { &mut *f.cast::<CallbackContext>() };
+    // Stop the session as soon as possible after a panic
+    if context.panic.is_some() {
+        return 0;
+    }
     let tensors = unsafe { std::slice::from_raw_parts(tensors.cast(), tensor_count) };
-    let f: TensorCallback = TensorCallback::from_ptr(f);
     let op = OperatorInfo {
         inner: op.cast(),
+        forward_type,
         __marker: PhantomData,
     };
-    let ret = f(tensors, op) as libc::c_int;
-
-    core::mem::forget(f);
-    ret
+    let callback = &mut context.callback;
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| callback(tensors, op))) {
+        Ok(ret) => ret as libc::c_int,
+        Err(panic) => {
+            context.panic = Some(panic);
+            0
+        }
+    }
 }
 
 /// A struct that holds information about an operator
-#[repr(transparent)]
 pub struct OperatorInfo<'op> {
     pub(crate) inner: *mut libc::c_void,
+    /// Raw `MNNForwardType` of the backend the operator runs on, -1 if unknown
+    pub(crate) forward_type: libc::c_int,
     pub(crate) __marker: PhantomData<&'op ()>,
 }
 
@@ -654,6 +1216,7 @@ impl core::fmt::Debug for OperatorInfo<'_> {
             .field("name", &self.name())
             .field("type", &self.type_name())
             .field("flops", &self.flops())
+            .field("backend", &self.backend())
             .finish()
     }
 }
@@ -673,6 +1236,15 @@ impl OperatorInfo<'_> {
     pub fn flops(&self) -> f32 {
         unsafe { mnn_sys::OperatorInfo_flops(self.inner) }
     }
+
+    /// Get the backend the operator runs on
+    ///
+    /// This is the backend of the first tensor passed to the callback (the inputs for the before
+    /// callback and the outputs for the after callback). `None` if the operator has no tensors or
+    /// MNN reports a backend this build doesn't know about.
+    pub fn backend(&self) -> Option<crate::ForwardType> {
+        crate::ForwardType::from_raw(self.forward_type)
+    }
 }
 
 #[test]
@@ -684,12 +1256,7 @@ fn test_run_session_with_callback_info_api() {
     let mut interpreter = Interpreter::from_file(&file).unwrap();
     let session = interpreter.create_session(ScheduleConfig::new()).unwrap();
     interpreter
-        .run_session_with_callback(
-            &session,
-            TensorCallback::identity(),
-            TensorCallback::identity(),
-            true,
-        )
+        .run_session_with_callback(&session, |_, _| true, |_, _| true, true)
         .unwrap();
 }
 
@@ -703,35 +1270,25 @@ fn check_whether_sync_actually_works() {
     let session = interpreter.create_session(ScheduleConfig::new()).unwrap();
     let time = std::time::Instant::now();
     interpreter
-        .run_session_with_callback(
-            &session,
-            TensorCallback::identity(),
-            TensorCallback::identity(),
-            false,
-        )
+        .run_session_with_callback(&session, |_, _| true, |_, _| true, false)
         .unwrap();
     let time = time.elapsed();
     let time2 = std::time::Instant::now();
     interpreter
-        .run_session_with_callback(
-            &session,
-            TensorCallback::identity(),
-            TensorCallback::identity(),
-            true,
-        )
+        .run_session_with_callback(&session, |_, _| true, |_, _| true, true)
         .unwrap();
     let time2 = time2.elapsed();
     assert!((time - time2) > std::time::Duration::from_millis(50));
 }
 
 #[test]
-#[ignore = "Fails on CI"]
 fn try_to_drop_interpreter_before_session() {
     let file = Path::new("tests/assets/realesr.mnn")
         .canonicalize()
         .unwrap();
     let mut interpreter = Interpreter::from_file(&file).unwrap();
     let session = interpreter.create_session(ScheduleConfig::new()).unwrap();
+    assert_eq!(interpreter.session_count(), 1);
     drop(interpreter);
     drop(session);
 }
@@ -0,0 +1,182 @@
+use crate::prelude::*;
+use mnn_sys::cpp;
+
+/// Mask of the geometry compute optimizations to enable
+///
+/// Default is [`GeometryComputeMask::ALL`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GeometryComputeMask(u32);
+
+impl GeometryComputeMask {
+    /// Fuse regions of the same op
+    pub const FUSE_REGION: Self =
+        Self(cpp::MNN_Interpreter_GeometryComputeMask_GEOMETRCOMPUTEMASK_FUSEREGION as u32);
+    /// Fuse regions across multiple ops
+    pub const FUSE_REGION_MULTI: Self =
+        Self(cpp::MNN_Interpreter_GeometryComputeMask_GEOMETRCOMPUTEMASK_FUSEREGION_MULTI as u32);
+    /// Use loop instead of raster if possible
+    pub const USE_LOOP: Self =
+        Self(cpp::MNN_Interpreter_GeometryComputeMask_GEOMETRCOMPUTEMASK_USELOOP as u32);
+    /// Cache the geometry compute result, required by
+    /// [`SessionMode::ResizeCheck`](crate::SessionMode::ResizeCheck) and
+    /// [`SessionMode::ResizeFix`](crate::SessionMode::ResizeFix)
+    pub const OPEN_CACHE: Self =
+        Self(cpp::MNN_Interpreter_GeometryComputeMask_GEOMETRCOMPUTEMASK_OPENCACHE as u32);
+    /// All optimizations
+    pub const ALL: Self =
+        Self(cpp::MNN_Interpreter_GeometryComputeMask_GEOMETRCOMPUTEMASK_ALL as u32);
+    /// No optimizations
+    pub const NONE: Self = Self(0);
+
+    /// Check whether all the bits of `other` are set in `self`
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Get the raw bits of the mask
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+}
+
+impl core::ops::BitOr for GeometryComputeMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for GeometryComputeMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl Default for GeometryComputeMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Hints for sessions created from an interpreter
+///
+/// Set with [`Interpreter::set_session_hint`](crate::Interpreter::set_session_hint), and only
+/// applies to sessions created afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionHint {
+    /// Maximum number of ops to tune asynchronously (GPU backends)
+    MaxTuningNumber(u32),
+    /// Strictly check the model file, default is `true`
+    StrictCheckModel(bool),
+    /// Number of winograd unit candidates (0..=3), default is 3
+    ///
+    /// Lower values use less memory at the expense of performance
+    WinogradMemoryLevel(u8),
+    /// Geometry compute optimizations, default is [`GeometryComputeMask::ALL`]
+    GeometryComputeMask(GeometryComputeMask),
+    /// Dynamic quantization options (0..=2), default is 0
+    /// - 0: Disabled
+    /// - 1: Quantize per batch
+    /// - 2: Quantize per tensor
+    DynamicQuantOptions(u8),
+    /// Capacity of the little cores relative to the big cores in percent (0..=100), default is 50
+    CpuLittleCoreDecreaseRate(u8),
+    /// Ids of the CPU cores to run on
+    CpuCoreIds(Vec<u32>),
+}
+
+impl SessionHint {
+    pub(crate) fn mode(&self) -> i32 {
+        (match self {
+            Self::MaxTuningNumber(_) => cpp::MNN_Interpreter_HintMode_MAX_TUNING_NUMBER,
+            Self::StrictCheckModel(_) => cpp::MNN_Interpreter_HintMode_STRICT_CHECK_MODEL,
+            Self::WinogradMemoryLevel(_) => cpp::MNN_Interpreter_HintMode_WINOGRAD_MEMORY_LEVEL,
+            Self::GeometryComputeMask(_) => cpp::MNN_Interpreter_HintMode_GEOMETRY_COMPUTE_MASK,
+            Self::DynamicQuantOptions(_) => cpp::MNN_Interpreter_HintMode_DYNAMIC_QUANT_OPTIONS,
+            Self::CpuLittleCoreDecreaseRate(_) => {
+                cpp::MNN_Interpreter_HintMode_CPU_LITTLECORE_DECREASE_RATE
+            }
+            Self::CpuCoreIds(_) => cpp::MNN_Interpreter_HintMode_CPU_CORE_IDS,
+        }) as i32
+    }
+
+    /// Check that the value of the hint is in range
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            Self::MaxTuningNumber(n) => {
+                ensure!(i32::try_from(*n).is_ok(), ErrorKind::InvalidSessionHint; format!("MaxTuningNumber {n} is out of range"));
+            }
+            Self::StrictCheckModel(_) => {}
+            Self::WinogradMemoryLevel(level) => {
+                ensure!(*level <= 3, ErrorKind::InvalidSessionHint; format!("WinogradMemoryLevel must be in 0..=3, got {level}"));
+            }
+            Self::GeometryComputeMask(mask) => {
+                ensure!(GeometryComputeMask::ALL.contains(*mask), ErrorKind::InvalidSessionHint; format!("Unknown bits in GeometryComputeMask {:#x}", mask.bits()));
+            }
+            Self::DynamicQuantOptions(options) => {
+                ensure!(*options <= 2, ErrorKind::InvalidSessionHint; format!("DynamicQuantOptions must be in 0..=2, got {options}"));
+            }
+            Self::CpuLittleCoreDecreaseRate(rate) => {
+                ensure!(*rate <= 100, ErrorKind::InvalidSessionHint; format!("CpuLittleCoreDecreaseRate must be in 0..=100, got {rate}"));
+            }
+            Self::CpuCoreIds(ids) => {
+                ensure!(!ids.is_empty(), ErrorKind::InvalidSessionHint; "CpuCoreIds must not be empty");
+                ensure!(ids.iter().all(|id| i32::try_from(*id).is_ok()), ErrorKind::InvalidSessionHint; format!("CpuCoreIds {ids:?} are out of range"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the hint to the interpreter, the hint must be validated first
+    pub(crate) fn apply(&self, interpreter: *mut mnn_sys::Interpreter) {
+        let mode = self.mode();
+        let value = match self {
+            Self::CpuCoreIds(ids) => {
+                let ids: Vec<i32> = ids.iter().map(|id| *id as i32).collect();
+                unsafe {
+                    mnn_sys::Interpreter_setSessionHintArray(
+                        interpreter,
+                        mode,
+                        ids.as_ptr(),
+                        ids.len(),
+                    )
+                };
+                return;
+            }
+            Self::MaxTuningNumber(n) => *n as i32,
+            Self::StrictCheckModel(strict) => *strict as i32,
+            Self::WinogradMemoryLevel(level) => (*level).into(),
+            Self::GeometryComputeMask(mask) => mask.bits() as i32,
+            Self::DynamicQuantOptions(options) => (*options).into(),
+            Self::CpuLittleCoreDecreaseRate(rate) => (*rate).into(),
+        };
+        unsafe { mnn_sys::Interpreter_setSessionHint(interpreter, mode, value) }
+    }
+}
+
+#[test]
+fn test_session_hint_validation() {
+    assert!(SessionHint::WinogradMemoryLevel(3).validate().is_ok());
+    assert!(SessionHint::WinogradMemoryLevel(4).validate().is_err());
+    assert!(SessionHint::DynamicQuantOptions(3).validate().is_err());
+    assert!(
+        SessionHint::CpuLittleCoreDecreaseRate(101)
+            .validate()
+            .is_err()
+    );
+    assert!(SessionHint::CpuCoreIds(vec![]).validate().is_err());
+    assert!(SessionHint::CpuCoreIds(vec![0, 1]).validate().is_ok());
+    assert!(
+        SessionHint::GeometryComputeMask(
+            GeometryComputeMask::FUSE_REGION | GeometryComputeMask::USE_LOOP
+        )
+        .validate()
+        .is_ok()
+    );
+    assert!(
+        SessionHint::GeometryComputeMask(GeometryComputeMask(0x10000))
+            .validate()
+            .is_err()
+    );
+}
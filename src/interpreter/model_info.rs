@@ -0,0 +1,206 @@
+//! Information about a model which is read directly from the model buffer without creating a
+//! session.
+use crate::{DimensionType, prelude::*};
+
+/// Description of an input or output tensor as it is declared in the model file
+///
+/// Models don't always declare everything about a tensor, so any missing information is `None`.
+/// The shape and type of outputs are only declared if the model stores a description of the
+/// tensor (e.g. models converted with a fixed input shape) or if the output is also an input,
+/// otherwise they're only known after a session has been created and resized.
+#[derive(Debug, Clone)]
+pub struct TensorDescriptor {
+    name: String,
+    shape: Option<Vec<i32>>,
+    halide_type: Option<mnn_sys::halide_type_t>,
+    dimension_type: Option<DimensionType>,
+}
+
+impl TensorDescriptor {
+    /// # Safety
+    /// The descriptor must be a valid pointer returned from the C api
+    unsafe fn from_raw(descriptor: &mnn_sys::TensorDescriptor) -> Self {
+        let name = unsafe { descriptor.name.to_cstr() }
+            .to_string_lossy()
+            .into_owned();
+        let shape = (descriptor.hasShape != 0).then(|| {
+            if descriptor.shapeSize == 0 {
+                Vec::new()
+            } else {
+                unsafe { core::slice::from_raw_parts(descriptor.shape, descriptor.shapeSize) }
+                    .to_vec()
+            }
+        });
+        Self {
+            name,
+            shape,
+            halide_type: (descriptor.hasType != 0).then_some(descriptor.type_),
+            dimension_type: (descriptor.hasDimensionType != 0)
+                .then(|| descriptor.dimensionType.into()),
+        }
+    }
+
+    /// Name of the tensor
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Declared shape of the tensor, dynamic dimensions are `-1`
+    pub fn shape(&self) -> Option<&[i32]> {
+        self.shape.as_deref()
+    }
+
+    /// Declared halide type of the tensor
+    pub fn halide_type(&self) -> Option<mnn_sys::halide_type_t> {
+        self.halide_type
+    }
+
+    /// Declared dimension type of the tensor
+    pub fn dimension_type(&self) -> Option<DimensionType> {
+        self.dimension_type
+    }
+
+    /// Check whether the declared type of the tensor matches `H`
+    ///
+    /// Returns `false` if the model doesn't declare the type
+    pub fn is_type_of<H: HalideType>(&self) -> bool {
+        let expected = mnn_sys::halide_type_of::<H>();
+        self.halide_type.is_some_and(|t| {
+            t.code == expected.code && t.bits == expected.bits && t.lanes == expected.lanes
+        })
+    }
+
+    /// Whether the tensor needs to be resized before it can be used
+    ///
+    /// This is true if the shape is not declared or if any of the dimensions is `-1`
+    pub fn is_dynamic(&self) -> bool {
+        self.shape
+            .as_ref()
+            .is_none_or(|shape| shape.iter().any(|d| *d < 0))
+    }
+
+    /// Indices of the dynamic (`-1`) dimensions of the declared shape
+    pub fn dynamic_axes(&self) -> Vec<usize> {
+        self.shape
+            .iter()
+            .flatten()
+            .enumerate()
+            .filter(|(_, d)| **d < 0)
+            .map(|(axis, _)| axis)
+            .collect()
+    }
+}
+
+/// Inputs, outputs and metadata of a model
+///
+/// Created with [`Interpreter::model_info`](crate::Interpreter::model_info)
+#[derive(Debug, Clone)]
+pub struct ModelInfo {
+    pub(crate) inputs: Vec<TensorDescriptor>,
+    pub(crate) outputs: Vec<TensorDescriptor>,
+    pub(crate) version: String,
+    pub(crate) biz_code: Option<String>,
+    pub(crate) uuid: Option<String>,
+}
+
+impl ModelInfo {
+    /// Inputs of the model
+    pub fn inputs(&self) -> &[TensorDescriptor] {
+        &self.inputs
+    }
+
+    /// Outputs of the model
+    pub fn outputs(&self) -> &[TensorDescriptor] {
+        &self.outputs
+    }
+
+    /// Get an input by its name
+    pub fn input(&self, name: impl AsRef<str>) -> Option<&TensorDescriptor> {
+        let name = name.as_ref();
+        self.inputs.iter().find(|t| t.name == name)
+    }
+
+    /// Get an output by its name
+    pub fn output(&self, name: impl AsRef<str>) -> Option<&TensorDescriptor> {
+        let name = name.as_ref();
+        self.outputs.iter().find(|t| t.name == name)
+    }
+
+    /// Version of MNN that the model was converted with
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Business code of the model
+    pub fn biz_code(&self) -> Option<&str> {
+        self.biz_code.as_deref()
+    }
+
+    /// uuid of the model
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+}
+
+/// Owner of a `TensorDescriptorArray` returned from the C api
+pub(crate) struct TensorDescriptorArray {
+    inner: *mut mnn_sys::TensorDescriptorArray,
+}
+
+impl Drop for TensorDescriptorArray {
+    fn drop(&mut self) {
+        unsafe { mnn_sys::destroyTensorDescriptorArray(self.inner) }
+    }
+}
+
+impl TensorDescriptorArray {
+    /// Describe the inputs and outputs of the model, the model buffer is only verified once
+    pub(crate) fn describe(interpreter: *const mnn_sys::Interpreter) -> Result<(Self, Self)> {
+        let mut inputs = core::ptr::null_mut();
+        let mut outputs = core::ptr::null_mut();
+        let ret =
+            unsafe { mnn_sys::Interpreter_describeModel(interpreter, &mut inputs, &mut outputs) };
+        ensure!(
+            ret != 0,
+            ErrorKind::InterpreterError;
+            "Failed to read the model buffer",
+            "The model might have been released with release_model"
+        );
+        Ok((Self { inner: inputs }, Self { inner: outputs }))
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<TensorDescriptor> {
+        let array = unsafe { &*self.inner };
+        if array.size == 0 {
+            return Vec::new();
+        }
+        unsafe { core::slice::from_raw_parts(array.tensors, array.size) }
+            .iter()
+            .map(|descriptor| unsafe { TensorDescriptor::from_raw(descriptor) })
+            .collect()
+    }
+}
+
+#[test]
+fn test_dynamic_axes() {
+    let descriptor = TensorDescriptor {
+        name: "input".into(),
+        shape: Some(vec![1, -1, -1, 3]),
+        halide_type: Some(mnn_sys::halide_type_of::<f32>()),
+        dimension_type: Some(DimensionType::NHWC),
+    };
+    assert!(descriptor.is_dynamic());
+    assert_eq!(descriptor.dynamic_axes(), [1, 2]);
+    assert!(descriptor.is_type_of::<f32>());
+    assert!(!descriptor.is_type_of::<u8>());
+
+    let descriptor = TensorDescriptor {
+        name: "output".into(),
+        shape: None,
+        halide_type: None,
+        dimension_type: None,
+    };
+    assert!(descriptor.is_dynamic());
+    assert!(descriptor.dynamic_axes().is_empty());
+    assert!(!descriptor.is_type_of::<f32>());
+}
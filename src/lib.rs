@@ -3,8 +3,8 @@
 //! Ergonomic rust bindings for [MNN](https://github.com/alibaba/MNN)  
 //!
 //! The main data structures used are [`Tensor`] and [`Interpreter`].   
-//! [Interpreter] should be thread safe and can be used to run multiple sessions concurrently.  
-//! [Send] / [Sync] is not implemented for Interpreter yet since we don't know how it will be used.  
+//! [Interpreter] is [Send] but not [Sync] and [Session] is neither.  
+//! To run multiple sessions of one model concurrently use a [SessionPool], which is [Send] + [Sync].  
 //!
 //! ![Codecov](https://img.shields.io/codecov/c/github/aftershootco/mnn-rs?link=https%3A%2F%2Fapp.codecov.io%2Fgithub%2Faftershootco%2Fmnn-rs)
 //! ![GitHub Actions Workflow Status](https://img.shields.io/github/actions/workflow/status/aftershootco/mnn-rs/build.yaml?link=https%3A%2F%2Fgithub.com%2Faftershootco%2Fmnn-rs%2Factions%2Fworkflows%2Fbuild.yaml)
@@ -37,6 +37,7 @@
 //! - `profile`: Enable profiling ( emits some profiling tracing events )
 //! - `tracing`: Enable tracing ( emits some tracing events )
 //! - `crt_static`: Link statically to the C runtime on windows (noop on other platforms)
+//! - `half`: Enable f16 and bf16 tensors using the [half](https://docs.rs/half) crate
 //! ## License
 //! This links to the MNN library which is licensed under the Apache License 2.0.  
 //! The rust bindings are licensed under the same Apache License 2.0.  
@@ -68,26 +69,61 @@ pub mod ffi {
 mod profile;
 
 pub mod backend;
+/// Zero copy inputs and outputs in memory of the caller
+pub mod binding;
+/// Cooperative cancellation of running sessions
+pub mod cancellation;
 /// Error handling
 pub mod error;
+/// Asynchronous completion of sessions
+pub mod future;
+/// High level inference with named inputs and outputs
+pub mod infer;
 /// MNN::Interpreter related items
 pub mod interpreter;
+/// Pool of sessions shared between threads
+pub mod pool;
+/// Per-operator profiling
+pub mod profiler;
+/// Shared backend runtime
+pub mod runtime;
 /// Schedule configuration
 pub mod schedule;
 /// MNN::Session related items
 pub mod session;
+/// Reusable host buffers for copying inputs and outputs
+pub mod staging;
 /// MNN::Tensor related items
 pub mod tensor;
 
 pub use backend::*;
+pub use binding::*;
+pub use cancellation::*;
 pub use error::*;
+pub use future::*;
+pub use infer::*;
 pub use interpreter::*;
+pub use pool::*;
+pub use profiler::*;
+pub use runtime::*;
 pub use schedule::*;
 pub use session::*;
+pub use staging::*;
 pub use tensor::*;
 
 pub use ffi::HalideType;
 pub use ffi::MapType;
+/// Re-export of the half crate for [`f16`](half::f16) and [`bf16`](half::bf16) tensors
+#[cfg(feature = "half")]
+pub use mnn_sys::half;
+
+/// The version of the linked MNN library
+pub fn version() -> &'static str {
+    // SAFETY: MNN returns a pointer to a static nul terminated string
+    unsafe { std::ffi::CStr::from_ptr(mnn_sys::getVersion()) }
+        .to_str()
+        .unwrap_or("unknown")
+}
 
 /// Re-export of commonly used items
 pub mod prelude {
@@ -0,0 +1,195 @@
+use crate::{Interpreter, ScheduleConfig, Session, prelude::*};
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// A pool of sessions created from a single interpreter which can be shared between threads
+///
+/// MNN allows running different sessions of the same interpreter concurrently, so the pool loads
+/// the model once and hands out one session per worker. A session is checked out with
+/// [`SessionPool::get`] and returned to the pool when the [`PooledSession`] guard is dropped.
+///
+/// ```no_run
+/// # use mnn::*;
+/// # fn main() -> Result<()> {
+/// let net = Interpreter::from_file("model.mnn")?;
+/// let pool = SessionPool::new(net, ScheduleConfig::new(), 4)?;
+/// std::thread::scope(|s| {
+///     for _ in 0..8 {
+///         s.spawn(|| -> Result<()> {
+///             let session = pool.get();
+///             let mut input = session.input::<f32>("input")?;
+///             input.fill(1.0);
+///             session.run()?;
+///             let output = session.output::<f32>("output")?;
+///             let _output = output.create_host_tensor_from_device(true);
+///             Ok(())
+///         });
+///     }
+/// });
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Note
+/// The interpreter isn't reachable once the pool is created, the pooled sessions are used through
+/// [`PooledSession::run`], [`PooledSession::input`] and [`PooledSession::output`] instead.
+#[derive(Debug)]
+pub struct SessionPool {
+    /// Sessions are declared first so they are dropped before the interpreter
+    sessions: Mutex<Vec<Session>>,
+    available: Condvar,
+    size: usize,
+    interpreter: Interpreter,
+}
+
+// SAFETY: Sessions are only handed out to one thread at a time through the mutex. The interpreter
+// is private and only used by `PooledSession` to run the checked out session and look up its
+// tensors. MNN documents `runSession` as thread safe for different sessions of the same
+// interpreter, and `getSessionInput` / `getSessionOutput` only read the maps of the given session.
+unsafe impl Send for SessionPool {}
+unsafe impl Sync for SessionPool {}
+
+impl SessionPool {
+    /// Create a pool with `size` sessions created from `config`
+    pub fn new(mut interpreter: Interpreter, config: ScheduleConfig, size: usize) -> Result<Self> {
+        ensure!(size > 0, ErrorKind::InterpreterError; "Session pool needs at least one session");
+        let sessions = (0..size)
+            .map(|_| interpreter.create_session(config.clone()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            sessions: Mutex::new(sessions),
+            available: Condvar::new(),
+            size,
+            interpreter,
+        })
+    }
+
+    /// Total number of sessions in the pool
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of sessions which are not checked out
+    pub fn available(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check out a session, blocks until one is available
+    pub fn get(&self) -> PooledSession<'_> {
+        let mut sessions = self.lock();
+        loop {
+            if let Some(session) = sessions.pop() {
+                return PooledSession::new(self, session);
+            }
+            sessions = self
+                .available
+                .wait(sessions)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Check out a session if one is available without blocking
+    pub fn try_get(&self) -> Option<PooledSession<'_>> {
+        self.lock()
+            .pop()
+            .map(|session| PooledSession::new(self, session))
+    }
+
+    /// Check out a session, waits at most `timeout` for one to become available
+    pub fn get_timeout(&self, timeout: Duration) -> Option<PooledSession<'_>> {
+        let deadline = Instant::now() + timeout;
+        let mut sessions = self.lock();
+        loop {
+            if let Some(session) = sessions.pop() {
+                return Some(PooledSession::new(self, session));
+            }
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+            sessions = self
+                .available
+                .wait_timeout(sessions, remaining)
+                .unwrap_or_else(PoisonError::into_inner)
+                .0;
+        }
+    }
+
+    /// A panic while holding the lock can't leave the list of sessions in an invalid state
+    fn lock(&self) -> MutexGuard<'_, Vec<Session>> {
+        self.sessions.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn release(&self, session: Session) {
+        self.lock().push(session);
+        self.available.notify_one();
+    }
+}
+
+/// A session checked out from a [`SessionPool`], it's returned to the pool on drop
+#[derive(Debug)]
+pub struct PooledSession<'p> {
+    pool: &'p SessionPool,
+    session: Option<Session>,
+}
+
+impl<'p> PooledSession<'p> {
+    fn new(pool: &'p SessionPool, session: Session) -> Self {
+        Self {
+            pool,
+            session: Some(session),
+        }
+    }
+
+    fn interpreter(&self) -> &'p Interpreter {
+        &self.pool.interpreter
+    }
+
+    /// Run the session
+    pub fn run(&self) -> Result<()> {
+        self.interpreter().run_session(self)
+    }
+
+    /// Get all input tensors of the session, see [`Interpreter::inputs`]
+    pub fn inputs(&self) -> crate::tensor::list::TensorList<'_> {
+        self.interpreter().inputs(self)
+    }
+
+    /// Get all output tensors of the session, see [`Interpreter::outputs`]
+    pub fn outputs(&self) -> crate::tensor::list::TensorList<'_> {
+        self.interpreter().outputs(self)
+    }
+
+    /// Get an input tensor of the session by name, see [`Interpreter::input`]
+    pub fn input<H: HalideType>(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<crate::Tensor<crate::RefMut<'_, crate::Device<H>>>> {
+        self.interpreter().input(self, name)
+    }
+
+    /// Get an output tensor of the session by name, see [`Interpreter::output`]
+    pub fn output<H: HalideType>(
+        &self,
+        name: impl AsRef<str>,
+    ) -> Result<crate::Tensor<crate::Ref<'_, crate::Device<H>>>> {
+        self.interpreter().output(self, name)
+    }
+}
+
+impl core::ops::Deref for PooledSession<'_> {
+    type Target = Session;
+
+    fn deref(&self) -> &Self::Target {
+        self.session
+            .as_ref()
+            .expect("Session is only taken when the guard is dropped")
+    }
+}
+
+impl Drop for PooledSession<'_> {
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.pool.release(session);
+        }
+    }
+}
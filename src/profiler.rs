@@ -0,0 +1,488 @@
+//! Per-operator profiling of a session
+//!
+//! The [`Profiler`] runs a session with [`Interpreter::run_session_with_callback`] and records the
+//! wall time of every operator, aggregated over multiple runs.
+//!
+//! The report can be exported as JSON or as a Chrome trace with the `serde` feature.
+//!
+//! ```no_run
+//! # use mnn::*;
+//! # fn main() -> Result<()> {
+//! let mut net = Interpreter::from_file("model.mnn")?;
+//! let session = net.create_session(ScheduleConfig::new())?;
+//! let report = Profiler::new()
+//!     .with_warmup(2)
+//!     .with_runs(10)
+//!     .profile(&mut net, &session)?;
+//! for summary in report.by_op_type() {
+//!     println!("{}: {:?}", summary.type_name, summary.mean);
+//! }
+//! # #[cfg(feature = "serde")]
+//! std::fs::write("trace.json", report.to_chrome_trace()).unwrap();
+//! # Ok(())
+//! # }
+//! ```
+use crate::{ForwardType, Interpreter, Session, prelude::*};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Runs a session multiple times and collects per-operator timings
+#[derive(Debug, Clone, Copy)]
+pub struct Profiler {
+    runs: usize,
+    warmup: usize,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    /// Create a profiler that runs the session once without warmup
+    pub fn new() -> Self {
+        Self { runs: 1, warmup: 0 }
+    }
+
+    /// Sets the number of profiled runs (at least 1)
+    pub fn set_runs(&mut self, runs: usize) -> &mut Self {
+        self.runs = runs.max(1);
+        self
+    }
+
+    /// Sets the number of profiled runs (at least 1)
+    pub fn with_runs(mut self, runs: usize) -> Self {
+        self.set_runs(runs);
+        self
+    }
+
+    /// Sets the number of runs before profiling starts, these are not recorded
+    pub fn set_warmup(&mut self, warmup: usize) -> &mut Self {
+        self.warmup = warmup;
+        self
+    }
+
+    /// Sets the number of runs before profiling starts, these are not recorded
+    pub fn with_warmup(mut self, warmup: usize) -> Self {
+        self.set_warmup(warmup);
+        self
+    }
+
+    /// Profile the session
+    ///
+    /// The inputs of the session need to be filled before calling this.
+    /// Returns an error if the session mode is [`SessionMode::Release`](crate::SessionMode::Release)
+    /// since operator callbacks are not called in that mode.
+    pub fn profile(
+        &self,
+        interpreter: &mut Interpreter,
+        session: &Session,
+    ) -> Result<ProfileReport> {
+        ensure!(
+            !session.has_mode(crate::SessionMode::Release),
+            ErrorKind::InterpreterError;
+            "Profiling needs operator callbacks which are disabled with SessionMode::Release"
+        );
+        for _ in 0..self.warmup {
+            interpreter.run_session(session)?;
+        }
+
+        let mut samples = Vec::with_capacity(self.runs);
+        let mut run_times = Vec::with_capacity(self.runs);
+        for _ in 0..self.runs {
+            let recorder = RefCell::new(Recorder::new());
+            let start = Instant::now();
+            interpreter.run_session_with_callback(
+                session,
+                |_, _| {
+                    recorder.borrow_mut().op_start = Some(Instant::now());
+                    true
+                },
+                |_, op| {
+                    recorder.borrow_mut().record(&op);
+                    true
+                },
+                true,
+            )?;
+            run_times.push(start.elapsed());
+            samples.push(recorder.into_inner().samples);
+        }
+        Ok(ProfileReport::new(run_times, samples))
+    }
+}
+
+/// Records the operators of a single run
+#[derive(Debug)]
+struct Recorder {
+    run_start: Instant,
+    op_start: Option<Instant>,
+    samples: Vec<OpSample>,
+}
+
+impl Recorder {
+    fn new() -> Self {
+        Self {
+            run_start: Instant::now(),
+            op_start: None,
+            samples: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, op: &crate::OperatorInfo) {
+        let end = Instant::now();
+        let start = self.op_start.take().unwrap_or(end);
+        self.samples.push(OpSample {
+            name: op.name().to_string_lossy().into_owned(),
+            type_name: op.type_name().to_string_lossy().into_owned(),
+            flops: op.flops(),
+            backend: op.backend(),
+            start: start.duration_since(self.run_start),
+            duration: end.duration_since(start),
+        });
+    }
+}
+
+/// A single execution of an operator
+#[derive(Debug, Clone)]
+struct OpSample {
+    name: String,
+    type_name: String,
+    flops: f32,
+    backend: Option<ForwardType>,
+    // Only needed for the chrome trace
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    start: Duration,
+    duration: Duration,
+}
+
+/// Timings of a single operator aggregated over all runs
+///
+/// Durations are serialized in microseconds
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OpStats {
+    /// Name of the operator
+    pub name: String,
+    /// Type of the operator
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_name: String,
+    /// Backend the operator ran on
+    pub backend: Option<ForwardType>,
+    /// Float operations of the operator in M
+    pub flops: f32,
+    /// Number of times the operator was executed
+    pub calls: usize,
+    /// Total time spent in the operator
+    #[cfg_attr(feature = "serde", serde(rename = "total_us", serialize_with = "micros"))]
+    pub total: Duration,
+    /// Shortest execution
+    #[cfg_attr(feature = "serde", serde(rename = "min_us", serialize_with = "micros"))]
+    pub min: Duration,
+    /// Longest execution
+    #[cfg_attr(feature = "serde", serde(rename = "max_us", serialize_with = "micros"))]
+    pub max: Duration,
+    /// Mean execution time
+    #[cfg_attr(feature = "serde", serde(rename = "mean_us", serialize_with = "micros"))]
+    pub mean: Duration,
+}
+
+/// Timings of all operators of the same type aggregated over all runs
+///
+/// Durations are serialized in microseconds
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct OpTypeSummary {
+    /// Type of the operators
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_name: String,
+    /// Number of operators of this type in the session
+    pub ops: usize,
+    /// Float operations of all operators of this type in M
+    pub flops: f32,
+    /// Total time spent in the operators of this type
+    #[cfg_attr(feature = "serde", serde(rename = "total_us", serialize_with = "micros"))]
+    pub total: Duration,
+    /// Mean time spent in the operators of this type per run
+    #[cfg_attr(feature = "serde", serde(rename = "mean_us", serialize_with = "micros"))]
+    pub mean: Duration,
+    /// Share of the total operator time in percent
+    pub percentage: f32,
+}
+
+/// The result of [`Profiler::profile`]
+#[derive(Debug, Clone)]
+pub struct ProfileReport {
+    run_times: Vec<Duration>,
+    ops: Vec<OpStats>,
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+    samples: Vec<Vec<OpSample>>,
+}
+
+impl ProfileReport {
+    fn new(run_times: Vec<Duration>, samples: Vec<Vec<OpSample>>) -> Self {
+        let mut ops: Vec<OpStats> = Vec::new();
+        let mut index: HashMap<(&str, &str), usize> = HashMap::new();
+        for sample in samples.iter().flatten() {
+            let key = (sample.name.as_str(), sample.type_name.as_str());
+            match index.get(&key) {
+                Some(&i) => {
+                    let stats = &mut ops[i];
+                    stats.calls += 1;
+                    stats.total += sample.duration;
+                    stats.min = stats.min.min(sample.duration);
+                    stats.max = stats.max.max(sample.duration);
+                }
+                None => {
+                    index.insert(key, ops.len());
+                    ops.push(OpStats {
+                        name: sample.name.clone(),
+                        type_name: sample.type_name.clone(),
+                        backend: sample.backend,
+                        flops: sample.flops,
+                        calls: 1,
+                        total: sample.duration,
+                        min: sample.duration,
+                        max: sample.duration,
+                        mean: Duration::ZERO,
+                    });
+                }
+            }
+        }
+        ops.iter_mut()
+            .for_each(|stats| stats.mean = stats.total / stats.calls as u32);
+        Self {
+            run_times,
+            ops,
+            samples,
+        }
+    }
+
+    /// Number of profiled runs
+    pub fn runs(&self) -> usize {
+        self.run_times.len()
+    }
+
+    /// Wall time of each run
+    pub fn run_times(&self) -> &[Duration] {
+        &self.run_times
+    }
+
+    /// Mean wall time of a run
+    pub fn mean_run_time(&self) -> Duration {
+        self.run_times.iter().sum::<Duration>() / self.runs().max(1) as u32
+    }
+
+    /// Per operator statistics in execution order
+    pub fn ops(&self) -> &[OpStats] {
+        &self.ops
+    }
+
+    /// Statistics grouped by operator type, sorted by total time (slowest first)
+    pub fn by_op_type(&self) -> Vec<OpTypeSummary> {
+        let total: Duration = self.ops.iter().map(|op| op.total).sum();
+        let mut summaries: Vec<OpTypeSummary> = Vec::new();
+        for op in &self.ops {
+            match summaries
+                .iter_mut()
+                .find(|summary| summary.type_name == op.type_name)
+            {
+                Some(summary) => {
+                    summary.ops += 1;
+                    summary.flops += op.flops;
+                    summary.total += op.total;
+                }
+                None => summaries.push(OpTypeSummary {
+                    type_name: op.type_name.clone(),
+                    ops: 1,
+                    flops: op.flops,
+                    total: op.total,
+                    mean: Duration::ZERO,
+                    percentage: 0.0,
+                }),
+            }
+        }
+        for summary in summaries.iter_mut() {
+            summary.mean = summary.total / self.runs().max(1) as u32;
+            summary.percentage = if total.is_zero() {
+                0.0
+            } else {
+                (summary.total.as_secs_f64() / total.as_secs_f64() * 100.0) as f32
+            };
+        }
+        summaries.sort_by(|a, b| b.total.cmp(&a.total));
+        summaries
+    }
+
+    /// Export the report as JSON
+    ///
+    /// Durations are in microseconds
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&JsonReport {
+            runs: self.runs(),
+            run_times_us: self.run_times.iter().map(|&t| as_micros(t)).collect(),
+            ops: &self.ops,
+            op_types: self.by_op_type(),
+        })
+        .expect("Failed to serialize the profile report")
+    }
+
+    /// Export the report in the Chrome trace event format, which can be opened with
+    /// `chrome://tracing` or [perfetto](https://ui.perfetto.dev)
+    ///
+    /// Each run is laid out one after another on the same track
+    #[cfg(feature = "serde")]
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace_events = Vec::new();
+        let mut offset = Duration::ZERO;
+        for (run, (samples, run_time)) in self.samples.iter().zip(&self.run_times).enumerate() {
+            trace_events.extend(samples.iter().map(|sample| TraceEvent {
+                name: &sample.name,
+                cat: &sample.type_name,
+                ph: "X",
+                ts: as_micros(offset + sample.start),
+                dur: as_micros(sample.duration),
+                pid: 0,
+                tid: 0,
+                args: TraceArgs {
+                    run,
+                    flops: sample.flops,
+                    backend: sample.backend,
+                },
+            }));
+            offset += *run_time;
+        }
+        serde_json::to_string(&ChromeTrace {
+            trace_events,
+            display_time_unit: "ms",
+        })
+        .expect("Failed to serialize the chrome trace")
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct JsonReport<'r> {
+    runs: usize,
+    run_times_us: Vec<f64>,
+    ops: &'r [OpStats],
+    op_types: Vec<OpTypeSummary>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChromeTrace<'r> {
+    trace_events: Vec<TraceEvent<'r>>,
+    display_time_unit: &'static str,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct TraceEvent<'r> {
+    name: &'r str,
+    cat: &'r str,
+    ph: &'static str,
+    ts: f64,
+    dur: f64,
+    pid: u32,
+    tid: u32,
+    args: TraceArgs,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+struct TraceArgs {
+    run: usize,
+    flops: f32,
+    backend: Option<ForwardType>,
+}
+
+#[cfg(feature = "serde")]
+fn as_micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1e6
+}
+
+#[cfg(feature = "serde")]
+fn micros<S: serde::Serializer>(
+    duration: &Duration,
+    serializer: S,
+) -> core::result::Result<S::Ok, S::Error> {
+    serializer.serialize_f64(as_micros(*duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(name: &str, type_name: &str, start: u64, duration: u64) -> OpSample {
+        OpSample {
+            name: name.into(),
+            type_name: type_name.into(),
+            flops: 1.0,
+            backend: Some(ForwardType::CPU),
+            start: Duration::from_micros(start),
+            duration: Duration::from_micros(duration),
+        }
+    }
+
+    fn report() -> ProfileReport {
+        ProfileReport::new(
+            vec![Duration::from_micros(40), Duration::from_micros(60)],
+            vec![
+                vec![
+                    sample("conv1", "Convolution", 0, 10),
+                    sample("relu1", "ReLU", 10, 5),
+                    sample("conv2", "Convolution", 15, 20),
+                ],
+                vec![
+                    sample("conv1", "Convolution", 0, 30),
+                    sample("relu1", "ReLU", 30, 5),
+                    sample("conv2", "Convolution", 35, 20),
+                ],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_op_stats() {
+        let report = report();
+        assert_eq!(report.runs(), 2);
+        assert_eq!(report.mean_run_time(), Duration::from_micros(50));
+        let conv1 = &report.ops()[0];
+        assert_eq!(conv1.name, "conv1");
+        assert_eq!(conv1.calls, 2);
+        assert_eq!(conv1.min, Duration::from_micros(10));
+        assert_eq!(conv1.max, Duration::from_micros(30));
+        assert_eq!(conv1.mean, Duration::from_micros(20));
+    }
+
+    #[test]
+    fn test_by_op_type() {
+        let summaries = report().by_op_type();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].type_name, "Convolution");
+        assert_eq!(summaries[0].ops, 2);
+        assert_eq!(summaries[0].total, Duration::from_micros(80));
+        assert_eq!(summaries[0].mean, Duration::from_micros(40));
+        assert!((summaries[0].percentage - 88.89).abs() < 0.01);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_export() {
+        let report = report();
+        let json = report.to_json();
+        assert!(json.starts_with("{\"runs\":2,"));
+        assert!(json.contains("\"name\":\"conv1\""));
+        let trace = report.to_chrome_trace();
+        assert!(trace.starts_with("{\"traceEvents\":["));
+        assert_eq!(trace.matches("\"ph\":\"X\"").count(), 6);
+        // The second run is placed after the first one
+        assert!(trace.contains("\"ts\":40.0,"));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::{ScheduleConfig, ScheduleConfigs, prelude::*};
+
+mod cache;
+pub use cache::CacheKey;
+
+/// Backend runtime (thread pool, memory pools, gpu context, etc) which can be shared between
+/// multiple interpreters and sessions.
+///
+/// By default every session creates its own runtime, which is wasteful when running a lot of
+/// small models in the same process. Create the runtime once, wrap it in an
+/// [`Arc`](std::sync::Arc) and pass it to
+/// [`Interpreter::create_session_with_runtime`](crate::Interpreter::create_session_with_runtime).
+///
+/// The runtime is kept alive by the sessions created with it.
+///
+/// # Note
+/// Sessions sharing a runtime also share its thread pool and memory, so MNN doesn't allow them to
+/// be used concurrently. The runtime holds a lock which is taken while one of its sessions is
+/// created, resized, run or released, so sessions on different threads sharing a runtime run one
+/// after the other instead of in parallel. Use separate runtimes for sessions that should run in
+/// parallel.
+///
+/// Running another session of the same runtime from inside a run callback deadlocks.
+///
+/// Session hints set on an interpreter are not applied to a shared runtime.
+#[derive(Debug)]
+pub struct Runtime {
+    pub(crate) inner: *mut mnn_sys::RuntimeInfo,
+    /// The schedule configs (and backend configs) need to outlive the runtime
+    pub(crate) __configs: ScheduleConfigs,
+    /// The cache loaded with [`Runtime::set_cache`] is referenced by the backends
+    pub(crate) __cache: Option<Vec<u8>>,
+    /// Serialises the use of the runtime by its sessions
+    pub(crate) lock: std::sync::Mutex<()>,
+    pub(crate) __marker: PhantomData<()>,
+}
+
+unsafe impl Send for Runtime {}
+/// Everything which touches the shared thread pool and memory goes through [`Runtime::lock`]
+unsafe impl Sync for Runtime {}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        unsafe { mnn_sys::RuntimeInfo_destroy(self.inner) }
+    }
+}
+
+impl Runtime {
+    /// Create a runtime from a single schedule config
+    pub fn new(config: ScheduleConfig) -> Result<Self> {
+        Self::from_configs([config])
+    }
+
+    /// Create a runtime from multiple schedule configs, the backend of each config will be
+    /// created in the runtime
+    pub fn from_configs(configs: impl IntoIterator<Item = ScheduleConfig>) -> Result<Self> {
+        let configs: ScheduleConfigs = configs
+            .into_iter()
+            .map(|config| config.validate().map(|_| config))
+            .collect::<Result<_>>()?;
+        ensure!(!configs.inner.is_empty(), ErrorKind::InterpreterError; "No schedule configs provided to create the runtime");
+        profile!("Creating runtime"; {
+            let runtime = unsafe { mnn_sys::Interpreter_createRuntime(configs.inner.as_ptr(), configs.inner.len()) };
+            ensure!(!runtime.is_null(), ErrorKind::InterpreterError; "Failed to create runtime", "Interpreter_createRuntime returned null");
+            Ok(Self {
+                inner: runtime,
+                __configs: configs,
+                __cache: None,
+                lock: std::sync::Mutex::new(()),
+                __marker: PhantomData,
+            })
+        })
+    }
+
+    /// Take the lock that serialises the sessions of the runtime
+    pub(crate) fn lock(&self) -> std::sync::MutexGuard<'_, ()> {
+        // The guarded data is `()`, a panic while holding the lock can't leave it inconsistent
+        self.lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Returns true if the runtime contains the backend
+    pub fn has_backend(&self, backend: crate::ForwardType) -> bool {
+        unsafe { mnn_sys::RuntimeInfo_hasBackend(self.inner, backend.to_raw()) != 0 }
+    }
+
+    /// Load a tuning cache blob previously returned by [`Runtime::cache`]
+    ///
+    /// This needs to be called before any session is created with the runtime, which is why it
+    /// takes `&mut self` (i.e. before wrapping the runtime in an [`Arc`](std::sync::Arc)).
+    ///
+    /// Returns [`ErrorKind::StaleCache`] if the blob was created for a different model, backend or
+    /// MNN version and [`ErrorKind::InvalidCache`] if it's malformed or the backend rejected it.
+    pub fn set_cache(&mut self, key: &CacheKey, blob: Vec<u8>) -> Result<()> {
+        ensure!(
+            self.has_backend(key.backend()),
+            ErrorKind::InvalidCache;
+            format!("The runtime has no {} backend", key.backend().to_str())
+        );
+        let cache = key.decode(&blob)?;
+        let ret = unsafe {
+            mnn_sys::RuntimeInfo_setCache(
+                self.inner,
+                key.backend().to_raw(),
+                cache.as_ptr().cast(),
+                cache.len(),
+            )
+        };
+        ensure!(
+            ret != 0,
+            ErrorKind::InvalidCache;
+            format!("The {} backend rejected the cache", key.backend().to_str())
+        );
+        self.__cache = Some(blob);
+        Ok(())
+    }
+
+    /// Get the tuning cache of the backend as a blob which can be stored and loaded again with
+    /// [`Runtime::set_cache`]
+    ///
+    /// The cache is filled when sessions created with this runtime are resized, so call this after
+    /// creating the sessions. Returns `None` if the backend doesn't have a cache (e.g. the cpu).
+    pub fn cache(&self, key: &CacheKey) -> Result<Option<Vec<u8>>> {
+        ensure!(
+            self.has_backend(key.backend()),
+            ErrorKind::InvalidCache;
+            format!("The runtime has no {} backend", key.backend().to_str())
+        );
+        let _guard = self.lock();
+        let mut size = 0;
+        let cache =
+            unsafe { mnn_sys::RuntimeInfo_getCache(self.inner, key.backend().to_raw(), &mut size) };
+        if cache.is_null() || size == 0 {
+            return Ok(None);
+        }
+        let cache = unsafe { core::slice::from_raw_parts(cache.cast::<u8>(), size) };
+        Ok(Some(key.encode(cache)))
+    }
+}
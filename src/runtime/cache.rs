@@ -0,0 +1,241 @@
+use crate::{ForwardType, Interpreter, ScheduleConfig, prelude::*};
+
+/// Magic bytes at the start of every cache blob
+const MAGIC: &[u8; 4] = b"MNNC";
+/// Version of the blob layout, bump when the header changes
+const FORMAT_VERSION: u32 = 1;
+
+/// Identifies what a tuning cache was created for
+///
+/// The tuning caches of the backends depend on the model, the backend and the MNN version, so
+/// the key is written into the header of every blob returned by [`Runtime::cache`](crate::Runtime::cache)
+/// and checked by [`Runtime::set_cache`](crate::Runtime::set_cache).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    model: String,
+    backend: ForwardType,
+    version: String,
+}
+
+impl CacheKey {
+    /// Create a key for the model identified by `model` running on `backend` with the current
+    /// MNN version
+    pub fn new(model: impl Into<String>, backend: ForwardType) -> Self {
+        Self {
+            model: model.into(),
+            backend,
+            version: crate::version().to_owned(),
+        }
+    }
+
+    /// Create a key from the uuid of the model loaded in the interpreter
+    ///
+    /// Returns an error if the model doesn't have a uuid, use [`CacheKey::new`] with an
+    /// identifier of your own in that case.
+    pub fn for_model(interpreter: &Interpreter, backend: ForwardType) -> Result<Self> {
+        let uuid = interpreter
+            .uuid()
+            .filter(|uuid| !uuid.is_empty())
+            .ok_or_else(|| {
+                error!(ErrorKind::InvalidCache)
+                    .attach_printable("The model has no uuid, use CacheKey::new instead")
+            })?;
+        Ok(Self::new(uuid, backend))
+    }
+
+    /// The model identifier
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// The backend the cache is for
+    pub fn backend(&self) -> ForwardType {
+        self.backend
+    }
+
+    /// The MNN version the cache is for
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Prepend the header for this key to the raw cache of the backend
+    pub(crate) fn encode(&self, cache: &[u8]) -> Vec<u8> {
+        let mut blob = Vec::with_capacity(cache.len() + self.model.len() + self.version.len() + 28);
+        blob.extend_from_slice(MAGIC);
+        blob.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        write_bytes(&mut blob, self.version.as_bytes());
+        write_bytes(&mut blob, self.model.as_bytes());
+        blob.extend_from_slice(&self.backend.to_raw().to_le_bytes());
+        blob.extend_from_slice(&(cache.len() as u64).to_le_bytes());
+        blob.extend_from_slice(cache);
+        blob
+    }
+
+    /// Check the header of the blob against this key and return the raw cache of the backend
+    pub(crate) fn decode<'b>(&self, blob: &'b [u8]) -> Result<&'b [u8]> {
+        let mut reader = Reader { blob };
+        ensure!(
+            reader.take(MAGIC.len())? == MAGIC,
+            ErrorKind::InvalidCache;
+            "Not a cache blob"
+        );
+        let format = reader.u32()?;
+        ensure!(
+            format == FORMAT_VERSION,
+            ErrorKind::StaleCache;
+            format!("Cache format {format}, expected {FORMAT_VERSION}")
+        );
+        let version = reader.bytes()?;
+        ensure!(
+            version == self.version.as_bytes(),
+            ErrorKind::StaleCache;
+            format!("Cache created with MNN {}, running {}", String::from_utf8_lossy(version), self.version)
+        );
+        let model = reader.bytes()?;
+        ensure!(
+            model == self.model.as_bytes(),
+            ErrorKind::StaleCache;
+            format!("Cache created for model {}, expected {}", String::from_utf8_lossy(model), self.model)
+        );
+        let backend = reader.u32()? as i32;
+        ensure!(
+            backend == self.backend.to_raw(),
+            ErrorKind::StaleCache;
+            format!("Cache created for backend {}, expected {}", ForwardType::from_raw(backend).map_or("unknown", ForwardType::to_str), self.backend.to_str())
+        );
+        let size = u64::from_le_bytes(reader.array()?) as usize;
+        let cache = reader.take(size)?;
+        ensure!(reader.blob.is_empty(), ErrorKind::InvalidCache; "Trailing bytes after the cache");
+        Ok(cache)
+    }
+}
+
+impl Interpreter {
+    /// Create a session which loads the tuning cache from memory
+    ///
+    /// This is the in-memory counterpart of [`Interpreter::set_cache_file`]. The session gets its
+    /// own [`Runtime`](crate::Runtime) created from `schedule`, `blob` is loaded into it with
+    /// [`Runtime::set_cache`](crate::Runtime::set_cache) before the session is created. Pass
+    /// `None` for the first run, the cache can be read with [`Interpreter::session_cache`] once
+    /// the session was resized.
+    ///
+    /// ```no_run
+    /// # use mnn::*;
+    /// # fn main() -> Result<()> {
+    /// let mut net = Interpreter::from_file("model.mnn")?;
+    /// let mut config = ScheduleConfig::new();
+    /// config.set_type(ForwardType::CPU);
+    /// let key = CacheKey::for_model(&net, ForwardType::CPU)?;
+    /// let session = net.create_session_with_cache(config.clone(), &key, None)?;
+    /// let blob = net.session_cache(&session, &key)?;
+    /// drop(session);
+    /// let session = net.create_session_with_cache(config, &key, blob)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_session_with_cache(
+        &mut self,
+        schedule: ScheduleConfig,
+        key: &CacheKey,
+        blob: Option<Vec<u8>>,
+    ) -> Result<crate::Session> {
+        let mut runtime = crate::Runtime::new(schedule.clone())?;
+        if let Some(blob) = blob {
+            runtime.set_cache(key, blob)?;
+        }
+        self.create_session_with_runtime(schedule, &std::sync::Arc::new(runtime))
+    }
+
+    /// Get the tuning cache of the session as a blob for [`Interpreter::create_session_with_cache`]
+    ///
+    /// Only sessions which were created with a [`Runtime`](crate::Runtime) have access to the
+    /// cache, e.g. with [`Interpreter::create_session_with_cache`], other sessions return
+    /// [`ErrorKind::InvalidCache`]. Returns `None` if the backend doesn't have a cache.
+    pub fn session_cache(
+        &self,
+        session: &crate::Session,
+        key: &CacheKey,
+    ) -> Result<Option<Vec<u8>>> {
+        let runtime = session.__runtime.as_ref().ok_or_else(|| {
+            error!(ErrorKind::InvalidCache).attach_printable(
+                "The session wasn't created with a runtime, use Interpreter::create_session_with_cache",
+            )
+        })?;
+        runtime.cache(key)
+    }
+}
+
+fn write_bytes(blob: &mut Vec<u8>, bytes: &[u8]) {
+    blob.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    blob.extend_from_slice(bytes);
+}
+
+struct Reader<'b> {
+    blob: &'b [u8],
+}
+
+impl<'b> Reader<'b> {
+    fn take(&mut self, len: usize) -> Result<&'b [u8]> {
+        ensure!(
+            self.blob.len() >= len,
+            ErrorKind::InvalidCache;
+            "Cache blob is truncated"
+        );
+        let (head, tail) = self.blob.split_at(len);
+        self.blob = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn bytes(&mut self) -> Result<&'b [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> CacheKey {
+        CacheKey::new("model", ForwardType::CPU)
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let blob = key().encode(b"tuned");
+        assert_eq!(key().decode(&blob).unwrap(), b"tuned");
+    }
+
+    #[test]
+    fn test_stale_cache() {
+        let blob = key().encode(b"tuned");
+        let other_model = CacheKey::new("other", ForwardType::CPU);
+        let err = other_model.decode(&blob).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::StaleCache));
+        let other_version = CacheKey {
+            version: "0.0.0".into(),
+            ..key()
+        };
+        let err = other_version.decode(&blob).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::StaleCache));
+    }
+
+    #[test]
+    fn test_invalid_cache() {
+        let blob = key().encode(b"tuned");
+        let err = key().decode(&blob[..blob.len() - 1]).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidCache));
+        let err = key().decode(b"garbage").unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidCache));
+    }
+}
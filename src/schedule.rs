@@ -33,7 +33,7 @@ use crate::{BackendConfig, prelude::*};
 ///
 /// The availability of certain variants depends on the features enabled during the build.
 /// For example, the `Metal` variant is only available if the `metal` feature is enabled.
-#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ForwardType {
     /// Use all available backends.
@@ -77,7 +77,7 @@ impl ForwardType {
         }
     }
 
-    fn from_mnn_sys(mode: MNNForwardType) -> Self {
+    pub(crate) fn from_mnn_sys(mode: MNNForwardType) -> Self {
         match mode {
             MNNForwardType::MNN_FORWARD_AUTO => ForwardType::Auto,
             MNNForwardType::MNN_FORWARD_ALL => ForwardType::All,
@@ -96,6 +96,46 @@ impl ForwardType {
         }
     }
 
+    /// Convert a raw `MNNForwardType` value (as returned by MNN as an int) to a `ForwardType`
+    ///
+    /// `MNN_FORWARD_CPU_EXTENSION` (e.g. the ARMv8.2 fp16 backend) is the CPU backend and is
+    /// reported as [`ForwardType::CPU`]. Returns `None` for values without a variant, e.g.
+    /// backends whose feature isn't enabled.
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        if raw == MNNForwardType::MNN_FORWARD_CPU_EXTENSION as i32 {
+            return Some(ForwardType::CPU);
+        }
+        [
+            ForwardType::All,
+            ForwardType::Auto,
+            ForwardType::CPU,
+            #[cfg(feature = "metal")]
+            ForwardType::Metal,
+            #[cfg(feature = "opencl")]
+            ForwardType::OpenCL,
+            #[cfg(feature = "vulkan")]
+            ForwardType::Vulkan,
+            #[cfg(feature = "coreml")]
+            ForwardType::CoreML,
+        ]
+        .into_iter()
+        .find(|forward_type| forward_type.to_raw() == raw)
+    }
+
+    /// Like [`ForwardType::from_raw`] but returns an error for unknown values
+    pub(crate) fn try_from_raw(raw: i32) -> Result<Self> {
+        Self::from_raw(raw).ok_or_else(|| {
+            error!(ErrorKind::NotSupported).attach_printable(format!(
+                "MNN reported the backend type {raw} which isn't enabled in this build"
+            ))
+        })
+    }
+
+    /// The raw `MNNForwardType` value, the inverse of [`ForwardType::from_raw`]
+    pub(crate) fn to_raw(self) -> i32 {
+        self.to_mnn_sys() as i32
+    }
+
     /// List all available `ForwardType` variants as string slices.
     fn list() -> Vec<&'static str> {
         vec![
@@ -330,6 +370,9 @@ impl ScheduleConfig {
 
     /// Sets the number of threads to be used for computation.
     ///
+    /// For GPU backends this holds the `MNN_GPU_*` mode flags instead. MNN uses a single thread
+    /// for 0, sessions can't be created with negative values.
+    ///
     /// # Arguments
     ///
     /// - `num_threads`: The number of threads to be used.
@@ -346,6 +389,23 @@ impl ScheduleConfig {
         self
     }
 
+    /// Gets the number of threads to be used for computation.
+    pub fn get_num_threads(&self) -> i32 {
+        unsafe { mnnsc_get_num_threads(self.inner) }
+    }
+
+    /// Check the values MNN would silently accept before creating a session with the config
+    pub(crate) fn validate(&self) -> Result<()> {
+        let num_threads = self.get_num_threads();
+        // MNN clamps 0 to a single thread, only negative values are invalid
+        ensure!(
+            num_threads >= 0,
+            ErrorKind::InvalidValue;
+            format!("The number of threads must not be negative, got {num_threads}")
+        );
+        Ok(())
+    }
+
     /// Sets the mode of computation.
     ///
     /// # Arguments
@@ -475,3 +535,22 @@ impl FromIterator<ScheduleConfig> for ScheduleConfigs {
 }
 
 unsafe impl Send for ScheduleConfigs {}
+
+#[test]
+fn test_forward_type_from_raw() {
+    assert_eq!(
+        ForwardType::from_raw(MNNForwardType::MNN_FORWARD_CPU as i32),
+        Some(ForwardType::CPU)
+    );
+    assert_eq!(
+        ForwardType::from_raw(MNNForwardType::MNN_FORWARD_CPU_EXTENSION as i32),
+        Some(ForwardType::CPU)
+    );
+    assert_eq!(
+        ForwardType::from_raw(ForwardType::Auto.to_raw()),
+        Some(ForwardType::Auto)
+    );
+    assert_eq!(ForwardType::from_raw(-1), None);
+    #[cfg(not(feature = "vulkan"))]
+    assert!(ForwardType::try_from_raw(MNNForwardType::MNN_FORWARD_VULKAN as i32).is_err());
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use std::sync::Arc;
 
 /// A session is a context in which a computation graph is executed.
 ///
@@ -7,15 +8,20 @@ use crate::prelude::*;
 pub struct Session {
     /// Pointer to the underlying MNN session.
     pub(crate) inner: *mut mnn_sys::Session,
-    /// Pointer to the underlying MNN interpreter
-    /// # Safety Note
-    /// Since the interpreter is actually not owned by session but it is a shared resource we can
-    /// reasonably assume that the interpreter will outlive the session. (This is not a compile
-    /// time gurantee yet)
-    /// TODO: Add a proper lifetime bound to ensure the interpreter outlives the session.
-    pub(crate) net: *mut mnn_sys::Interpreter,
+    /// Shared handle to the underlying MNN interpreter
+    ///
+    /// The session keeps the net alive so dropping the [`Interpreter`](crate::Interpreter) before
+    /// the session is safe, the net is released together with the last session.
+    pub(crate) net: Arc<crate::interpreter::NetHandle>,
+    /// The session modes set on the interpreter when the session was created
+    pub(crate) modes: Vec<crate::SessionMode>,
+    /// Shared runtime the session was created with, if any
+    pub(crate) __runtime: Option<Arc<crate::Runtime>>,
     /// Internal session configurations.
     pub(crate) __session_internals: crate::SessionInternals,
+    /// Outputs of the last [`run_session_async`](crate::Interpreter::run_session_async) which
+    /// aren't ready yet
+    pub(crate) pending: core::cell::RefCell<Option<std::sync::Arc<crate::future::Pending>>>,
     /// Marker to ensure the struct is not Send or Sync.
     pub(crate) __marker: PhantomData<()>,
 }
@@ -29,11 +35,71 @@ pub enum SessionInternals {
     MultiSession(crate::ScheduleConfigs),
 }
 
+impl SessionInternals {
+    /// The forward types requested by the schedule configs, one for each path
+    pub(crate) fn requested_types(&self) -> Vec<crate::ForwardType> {
+        match self {
+            SessionInternals::Single(config) => vec![config.get_type()],
+            SessionInternals::MultiSession(configs) => configs
+                .inner
+                .iter()
+                .map(|config| unsafe {
+                    crate::ForwardType::from_mnn_sys(mnn_sys::mnnsc_get_type(config.cast_mut()))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Snapshot of what MNN actually scheduled for a session
+///
+/// Created with [`Interpreter::session_info`](crate::Interpreter::session_info)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionInfo {
+    /// Memory usage of the session in MB
+    pub memory: f32,
+    /// Float operations needed by the session in M
+    pub flops: f32,
+    /// Backends of the runtimes that were actually created, including the backup CPU runtime
+    pub backends: Vec<crate::ForwardType>,
+    /// Raw `MNNForwardType` values of backends which were created but aren't enabled in this
+    /// build, they are left out of [`backends`](Self::backends)
+    pub unknown_backends: Vec<i32>,
+    /// Backends that were requested by the [`ScheduleConfig`](crate::ScheduleConfig), one for each path
+    pub requested: Vec<crate::ForwardType>,
+    /// Number of threads used by the session
+    pub thread_number: u32,
+    /// The resize status of the session
+    pub resize_status: crate::ResizeStatus,
+}
+
 impl Session {
+    /// Returns true if the session was created with `mode`
+    ///
+    /// Modes set on the interpreter afterwards don't apply to the session.
+    pub(crate) fn has_mode(&self, mode: crate::SessionMode) -> bool {
+        self.modes.contains(&mode)
+    }
+
+    /// Block until the outputs of a pending asynchronous run are ready
+    pub(crate) fn wait_pending(&self) {
+        if let Some(pending) = self.pending.borrow_mut().take() {
+            pending.wait();
+        }
+    }
+
+    /// Take the lock of the shared runtime the session was created with, if any
+    pub(crate) fn lock_runtime(&self) -> Option<std::sync::MutexGuard<'_, ()>> {
+        self.__runtime.as_ref().map(|runtime| runtime.lock())
+    }
+
     /// Calls the destroy function on the underlying MNN session.
     pub fn destroy(&mut self) {
+        self.wait_pending();
+        let _guard = self.lock_runtime();
         unsafe {
-            mnn_sys::Interpreter_releaseSession(self.net, self.inner);
+            mnn_sys::Interpreter_releaseSession(self.net.as_ptr(), self.inner);
         }
         // unsafe { mnn_sys::Session_destroy(self.inner) }
     }
@@ -0,0 +1,184 @@
+use crate::{DimensionType, Interpreter, RawTensor, Session, TensorShape, prelude::*};
+use std::collections::HashMap;
+
+/// Host tensors which are reused to copy data in and out of a session
+///
+/// The data is in NCHW, or NHWC for NHWC tensors, independent of how the backend stores it.
+///
+/// [`Tensor::create_host_tensor_from_device`](crate::Tensor::create_host_tensor_from_device)
+/// allocates a new host tensor every time. The staging buffers keep one host tensor per input
+/// and output alive across runs and only allocate a new one when the shape, type or layout of the
+/// device tensor changed, e.g. after [`Interpreter::resize_session`].
+///
+/// ```no_run
+/// # use mnn::*;
+/// # fn main() -> Result<()> {
+/// let mut net = Interpreter::from_file("model.mnn")?;
+/// let session = net.create_session(ScheduleConfig::new())?;
+/// let mut staging = StagingBuffers::new();
+/// let mut output = vec![0.0f32; 3 * 256 * 256];
+/// for frame in [vec![0.0f32; 3 * 64 * 64], vec![1.0f32; 3 * 64 * 64]] {
+///     staging.copy_in(&net, &session, "input", &frame)?;
+///     net.run_session(&session)?;
+///     staging.copy_out(&net, &session, "output", &mut output)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct StagingBuffers {
+    inputs: HashMap<String, Staged>,
+    outputs: HashMap<String, Staged>,
+}
+
+impl StagingBuffers {
+    /// Create empty staging buffers, the host tensors are allocated on first use
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copy `data` into the input tensor `name` of the session
+    pub fn copy_in<H: HalideType>(
+        &mut self,
+        interpreter: &Interpreter,
+        session: &Session,
+        name: impl AsRef<str>,
+        data: &[H],
+    ) -> Result<()> {
+        let name = name.as_ref();
+        let mut tensor = interpreter.raw_input(session, name)?;
+        let staged = Staged::get::<H>(&mut self.inputs, &tensor, name, data.len())?;
+        // SAFETY: The type and the number of elements were checked against the host tensor
+        unsafe { staged.host.unchecked_host_bytes() }.copy_from_slice(unsafe {
+            core::slice::from_raw_parts(data.as_ptr().cast(), core::mem::size_of_val(data))
+        });
+        tensor.copy_from_host_tensor(&staged.host)
+    }
+
+    /// Copy the output tensor `name` of the session into `data`
+    pub fn copy_out<H: HalideType>(
+        &mut self,
+        interpreter: &Interpreter,
+        session: &Session,
+        name: impl AsRef<str>,
+        data: &mut [H],
+    ) -> Result<()> {
+        let name = name.as_ref();
+        let tensor = interpreter.raw_output(session, name)?;
+        let staged = Staged::get::<H>(&mut self.outputs, &tensor, name, data.len())?;
+        tensor.copy_to_host_tensor(&mut staged.host)?;
+        // SAFETY: The type and the number of elements were checked against the host tensor
+        unsafe {
+            core::slice::from_raw_parts_mut(data.as_mut_ptr().cast(), core::mem::size_of_val(data))
+        }
+        .copy_from_slice(unsafe { staged.host.unchecked_host_bytes() });
+        Ok(())
+    }
+
+    /// Number of host tensors currently allocated
+    pub fn len(&self) -> usize {
+        self.inputs.len() + self.outputs.len()
+    }
+
+    /// Returns true if no host tensors are allocated
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Free all host tensors
+    pub fn clear(&mut self) {
+        self.inputs.clear();
+        self.outputs.clear();
+    }
+}
+
+/// An owned host tensor with the shape, type and layout of the device tensor it was created from
+struct Staged {
+    host: RawTensor<'static>,
+    shape: TensorShape,
+    halide_type: mnn_sys::halide_type_t,
+    layout: DimensionType,
+}
+
+impl core::fmt::Debug for Staged {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Staged")
+            .field("shape", &self.shape)
+            .field("type", &self.halide_type)
+            .field("layout", &self.layout)
+            .finish()
+    }
+}
+
+impl Drop for Staged {
+    fn drop(&mut self) {
+        unsafe { mnn_sys::Tensor_destroy(self.host.inner) }
+    }
+}
+
+impl Staged {
+    /// Whether the host tensor was created for a device tensor with this shape, type and layout
+    fn matches(
+        &self,
+        shape: &TensorShape,
+        halide_type: mnn_sys::halide_type_t,
+        layout: DimensionType,
+    ) -> bool {
+        self.shape.as_ref() == shape.as_ref()
+            && self.halide_type.code == halide_type.code
+            && self.halide_type.bits == halide_type.bits
+            && self.halide_type.lanes == halide_type.lanes
+            && self.layout == layout
+    }
+
+    /// Get the host tensor for `tensor`, allocating a new one if the shape, type or layout changed
+    fn get<'b, H: HalideType>(
+        buffers: &'b mut HashMap<String, Staged>,
+        tensor: &RawTensor,
+        name: &str,
+        len: usize,
+    ) -> Result<&'b mut Staged> {
+        ensure!(
+            tensor.is_type_of::<H>(),
+            ErrorKind::HalideTypeMismatch {
+                got: std::any::type_name::<H>(),
+            };
+            format!("Tensor \"{name}\" is of type {:?}", tensor.get_type())
+        );
+        ensure!(
+            !tensor.is_dynamic_unsized(),
+            ErrorKind::DynamicTensorError;
+            format!("Tensor \"{name}\" needs a shape")
+        );
+        ensure!(
+            tensor.element_size() == len,
+            ErrorKind::SizeMismatch {
+                expected: tensor.element_size(),
+                got: len,
+            };
+            format!("Tensor \"{name}\" has shape {:?}", tensor.shape())
+        );
+        let shape = tensor.shape();
+        let halide_type = tensor.get_type();
+        let layout = tensor.layout();
+        let reuse = buffers
+            .get(name)
+            .is_some_and(|staged| staged.matches(&shape, halide_type, layout));
+        if !reuse {
+            // NC4HW4 tensors are staged as NCHW, MNN converts while copying
+            let host = tensor.try_create_plain_host_tensor()?;
+            buffers.insert(
+                name.to_owned(),
+                Staged {
+                    host,
+                    shape,
+                    halide_type,
+                    layout,
+                },
+            );
+        }
+        Ok(buffers
+            .get_mut(name)
+            .expect("Staging buffer was inserted above"))
+    }
+}
@@ -2,9 +2,16 @@ use crate::prelude::*;
 use core::marker::PhantomData;
 use mnn_sys::*;
 use std::borrow::Borrow;
+mod device;
+mod layout;
 pub(crate) mod list;
+mod map;
 mod raw;
+mod view;
+pub use device::{CpuDeviceInfo, DeviceInfo};
+pub use map::{MappedTensor, MappedTensorMut};
 pub use raw::RawTensor;
+pub use view::TensorView;
 
 use mnn_sys::HalideType;
 
@@ -256,7 +263,15 @@ where
     }
     /// Copies the data from a host tensor to the self tensor
     pub fn copy_from_host_tensor(&mut self, tensor: &Tensor<Host<T::H>>) -> Result<()> {
-        assert_eq!(self.size(), tensor.size(), "Tensor sizes do not match");
+        // Compare the elements, NC4HW4 tensors take more bytes than the same NCHW tensor
+        ensure!(
+            self.element_size() == tensor.element_size(),
+            ErrorKind::SizeMismatch {
+                expected: self.element_size(),
+                got: tensor.element_size(),
+            };
+            format!("Tensor shapes {:?} and {:?} do not match", self.shape(), tensor.shape())
+        );
         let ret = unsafe { Tensor_copyFromHostTensor(self.tensor, tensor.tensor) };
         crate::ensure!(ret != 0, ErrorKind::TensorCopyFailed(ret));
         Ok(())
@@ -264,7 +279,14 @@ where
 
     /// Copies the data from the self tensor to a host tensor
     pub fn copy_to_host_tensor(&self, tensor: &mut Tensor<Host<T::H>>) -> Result<()> {
-        assert_eq!(self.size(), tensor.size(), "Tensor sizes do not match");
+        ensure!(
+            self.element_size() == tensor.element_size(),
+            ErrorKind::SizeMismatch {
+                expected: self.element_size(),
+                got: tensor.element_size(),
+            };
+            format!("Tensor shapes {:?} and {:?} do not match", self.shape(), tensor.shape())
+        );
         let ret = unsafe { Tensor_copyToHostTensor(self.tensor, tensor.tensor) };
         crate::ensure!(ret != 0, ErrorKind::TensorCopyFailed(ret));
         Ok(())
@@ -275,9 +297,16 @@ where
         unsafe { Tensor_deviceId(self.tensor) }
     }
 
+    /// Try to get the shape of the tensor
+    ///
+    /// Returns an error if the tensor has more than [`MAX_TENSOR_DIMS`] dimensions
+    pub fn try_shape(&self) -> Result<TensorShape> {
+        TensorShape::of(self.tensor)
+    }
+
     /// Get the shape of the tensor
     pub fn shape(&self) -> TensorShape {
-        unsafe { Tensor_shape(self.tensor) }.into()
+        self.try_shape().expect("Failed to get the tensor shape")
     }
 
     /// Get the dimensions of the tensor
@@ -380,30 +409,43 @@ impl<T: MutableTensorType> Tensor<T>
 where
     T::H: HalideType,
 {
-    /// Fill the tensor with the specified value
-    pub fn fill(&mut self, value: T::H)
+    /// Try to fill the tensor with the specified value
+    pub fn try_fill(&mut self, value: T::H) -> Result<()>
     where
         T::H: Copy,
     {
         if T::host() {
             let size = self.element_size();
-            assert!(self.is_type_of::<T::H>());
+            ensure!(
+                self.is_type_of::<T::H>(),
+                ErrorKind::HalideTypeMismatch {
+                    got: std::any::type_name::<T::H>(),
+                }
+            );
             let result: &mut [T::H] = unsafe {
                 let data = mnn_sys::Tensor_host_mut(self.tensor).cast();
                 core::slice::from_raw_parts_mut(data, size)
             };
             result.fill(value);
+            Ok(())
         } else if T::device() {
             let shape = self.shape();
             let dm_type = self.get_dimension_type();
-            let mut host = Tensor::new(shape, dm_type);
-            host.fill(value);
+            let mut host = Tensor::<Host<T::H>>::try_new(shape, dm_type)?;
+            host.try_fill(value)?;
             self.copy_from_host_tensor(&host)
-                .expect("Failed to copy data from host tensor");
         } else {
             unreachable!()
         }
     }
+
+    /// Fill the tensor with the specified value
+    pub fn fill(&mut self, value: T::H)
+    where
+        T::H: Copy,
+    {
+        self.try_fill(value).expect("Failed to fill tensor")
+    }
 }
 
 impl<T: HostTensorType> Tensor<T>
@@ -412,7 +454,29 @@ where
 {
     /// Try to map the device tensor to the host memory and get the slice
     pub fn try_host(&self) -> Result<&[T::H]> {
-        let size = self.element_size();
+        self.try_host_with_len(self.element_size())
+    }
+
+    /// Try to map the device tensor to the host memory and get the mutable slice
+    pub fn try_host_mut(&mut self) -> Result<&mut [T::H]> {
+        self.try_host_mut_with_len(self.element_size())
+    }
+
+    /// Try to get the host memory slice of the tensor including the channel padding of
+    /// [`DimensionType::NC4HW4`]
+    ///
+    /// For other layouts this is the same as [`Tensor::try_host`].
+    pub fn try_host_padded(&self) -> Result<&[T::H]> {
+        self.try_host_with_len(self.padded_len())
+    }
+
+    /// Try to get the mutable host memory slice of the tensor including the channel padding of
+    /// [`DimensionType::NC4HW4`]
+    pub fn try_host_padded_mut(&mut self) -> Result<&mut [T::H]> {
+        self.try_host_mut_with_len(self.padded_len())
+    }
+
+    fn try_host_with_len(&self, size: usize) -> Result<&[T::H]> {
         ensure!(
             self.is_type_of::<T::H>(),
             ErrorKind::HalideTypeMismatch {
@@ -426,9 +490,7 @@ where
         Ok(result)
     }
 
-    /// Try to map the device tensor to the host memory and get the mutable slice
-    pub fn try_host_mut(&mut self) -> Result<&mut [T::H]> {
-        let size = self.element_size();
+    fn try_host_mut_with_len(&mut self, size: usize) -> Result<&mut [T::H]> {
         ensure!(
             self.is_type_of::<T::H>(),
             ErrorKind::HalideTypeMismatch {
@@ -444,7 +506,18 @@ where
         Ok(result)
     }
 
+    /// Number of elements in the host memory, including the channel padding of NC4HW4
+    fn padded_len(&self) -> usize {
+        match self.layout() {
+            DimensionType::CaffeC4 => self.size() / core::mem::size_of::<T::H>(),
+            _ => self.element_size(),
+        }
+    }
+
     /// Get the host memory slice of the tensor
+    ///
+    /// For [`DimensionType::NC4HW4`] tensors the slice has the length of the unpadded tensor, use
+    /// [`Tensor::host_padded`] to get all of the packed channels.
     pub fn host(&self) -> &[T::H] {
         self.try_host().expect("Failed to get tensor host")
     }
@@ -453,6 +526,20 @@ where
     pub fn host_mut(&mut self) -> &mut [T::H] {
         self.try_host_mut().expect("Failed to get tensor host_mut")
     }
+
+    /// Get the host memory slice of the tensor including the channel padding of
+    /// [`DimensionType::NC4HW4`]
+    pub fn host_padded(&self) -> &[T::H] {
+        self.try_host_padded()
+            .expect("Failed to get tensor host_padded")
+    }
+
+    /// Get the mutable host memory slice of the tensor including the channel padding of
+    /// [`DimensionType::NC4HW4`]
+    pub fn host_padded_mut(&mut self) -> &mut [T::H] {
+        self.try_host_padded_mut()
+            .expect("Failed to get tensor host_padded_mut")
+    }
 }
 
 impl<T: DeviceTensorType> Tensor<T>
@@ -466,18 +553,27 @@ where
         }
     }
 
-    /// Create a host tensor from the device tensor with same dimensions and data type and
+    /// Try to create a host tensor from the device tensor with same dimensions and data type and
     /// optionally copy the data from the device tensor
-    pub fn create_host_tensor_from_device(&self, copy_data: bool) -> Tensor<Host<T::H>> {
+    pub fn try_create_host_tensor_from_device(
+        &self,
+        copy_data: bool,
+    ) -> Result<Tensor<Host<T::H>>> {
         let shape = self.shape();
         let dm_type = self.get_dimension_type();
-        let mut out = Tensor::new(shape, dm_type);
+        let mut out = Tensor::try_new(shape, dm_type)?;
 
         if copy_data {
-            self.copy_to_host_tensor(&mut out)
-                .expect("Failed to copy data from device tensor");
+            self.copy_to_host_tensor(&mut out)?;
         }
-        out
+        Ok(out)
+    }
+
+    /// Create a host tensor from the device tensor with same dimensions and data type and
+    /// optionally copy the data from the device tensor
+    pub fn create_host_tensor_from_device(&self, copy_data: bool) -> Tensor<Host<T::H>> {
+        self.try_create_host_tensor_from_device(copy_data)
+            .expect("Failed to create host tensor from device tensor")
     }
 }
 
@@ -485,9 +581,11 @@ impl<T: OwnedTensorType> Tensor<T>
 where
     T::H: HalideType,
 {
-    /// Create a new tensor with the specified shape and dimension type
-    pub fn new(shape: impl AsTensorShape, dm_type: DimensionType) -> Self {
-        let shape = shape.as_tensor_shape();
+    /// Try to create a new tensor with the specified shape and dimension type
+    ///
+    /// Returns an error if the shape has more than [`MAX_TENSOR_DIMS`] dimensions
+    pub fn try_new(shape: impl AsTensorShape, dm_type: DimensionType) -> Result<Self> {
+        let shape = shape.as_tensor_shape()?;
         let tensor = unsafe {
             if T::device() {
                 Tensor_createDevice(
@@ -507,10 +605,18 @@ where
             }
         };
         debug_assert!(!tensor.is_null());
-        Self {
+        Ok(Self {
             tensor,
             __marker: PhantomData,
-        }
+        })
+    }
+
+    /// Create a new tensor with the specified shape and dimension type
+    ///
+    /// # Panics
+    /// Panics if the shape has more than [`MAX_TENSOR_DIMS`] dimensions
+    pub fn new(shape: impl AsTensorShape, dm_type: DimensionType) -> Self {
+        Self::try_new(shape, dm_type).expect("Failed to create tensor")
     }
 }
 
@@ -527,14 +633,38 @@ where
     }
 }
 
+/// The maximum number of dimensions a [`TensorShape`] can hold
+///
+/// This is the most MNN supports, it can't create tensors with more dimensions either.
+pub const MAX_TENSOR_DIMS: usize = mnn_sys::TENSOR_SHAPE_MAX_DIMS as usize;
+
 /// A tensor shape
 #[derive(Clone, Copy)]
 #[repr(C)]
 pub struct TensorShape {
-    pub(crate) shape: [i32; 4],
+    pub(crate) shape: [i32; MAX_TENSOR_DIMS],
     pub(crate) size: usize,
 }
 
+impl TensorShape {
+    /// Read the shape of `tensor`, failing instead of truncating it
+    pub(crate) fn of(tensor: *const mnn_sys::Tensor) -> Result<Self> {
+        let mut shape = mnn_sys::TensorShape {
+            shape: [1; MAX_TENSOR_DIMS],
+            size: 0,
+        };
+        let ret = unsafe { mnn_sys::Tensor_shape(tensor, &mut shape) };
+        ensure!(
+            ret != 0,
+            ErrorKind::TooManyDimensions {
+                max: MAX_TENSOR_DIMS,
+                got: unsafe { mnn_sys::Tensor_dimensions(tensor) }.max(0) as usize,
+            }
+        );
+        Ok(shape.into())
+    }
+}
+
 impl From<mnn_sys::TensorShape> for TensorShape {
     fn from(value: mnn_sys::TensorShape) -> Self {
         Self {
@@ -590,32 +720,44 @@ impl core::fmt::Debug for TensorShape {
 /// A trait to convert any array-like type to a tensor shape
 pub trait AsTensorShape {
     /// Convert the array-like type to a tensor shape
-    fn as_tensor_shape(&self) -> TensorShape;
+    ///
+    /// Returns an error if the shape has more than [`MAX_TENSOR_DIMS`] dimensions
+    fn as_tensor_shape(&self) -> Result<TensorShape>;
 }
 
 impl<T: AsRef<[i32]>> AsTensorShape for T {
-    fn as_tensor_shape(&self) -> TensorShape {
+    fn as_tensor_shape(&self) -> Result<TensorShape> {
         let this = self.as_ref();
-        let size = std::cmp::min(this.len(), 4);
-        let mut shape = [1; 4];
-        shape[..size].copy_from_slice(&this[..size]);
-        TensorShape { shape, size }
+        let size = this.len();
+        ensure!(
+            size <= MAX_TENSOR_DIMS,
+            ErrorKind::TooManyDimensions {
+                max: MAX_TENSOR_DIMS,
+                got: size
+            };
+            format!("Shape: {:?}", this)
+        );
+        let mut shape = [1; MAX_TENSOR_DIMS];
+        shape[..size].copy_from_slice(this);
+        Ok(TensorShape { shape, size })
     }
 }
 
 impl AsTensorShape for TensorShape {
-    fn as_tensor_shape(&self) -> TensorShape {
-        *self
+    fn as_tensor_shape(&self) -> Result<TensorShape> {
+        Ok(*self)
     }
 }
 
 #[cfg(test)]
 mod as_tensor_shape_tests {
-    use super::AsTensorShape;
+    use super::{AsTensorShape, MAX_TENSOR_DIMS};
     macro_rules! shape_test {
         ($t:ty, $kind: expr, $value: expr) => {
             eprintln!("Testing {} with {} shape", stringify!($t), $kind);
-            $value.as_tensor_shape();
+            let value = $value;
+            let shape = value.as_tensor_shape().unwrap();
+            assert_eq!(&*shape, &value[..]);
         };
     }
     #[test]
@@ -627,12 +769,22 @@ mod as_tensor_shape_tests {
     fn as_tensor_shape_test_array() {
         shape_test!([i32; 3], "small", [1, 2, 3]);
         shape_test!([i32; 5], "large", [12, 23, 34, 45, 67]);
+        shape_test!([i32; 6], "rank 6", [1, 2, 3, 4, 5, 6]);
     }
     #[test]
     fn as_tensor_shape_test_ref() {
         shape_test!(&[i32], "small", &[1, 2, 3]);
         shape_test!(&[i32], "large", &[12, 23, 34, 45, 67]);
     }
+    #[test]
+    fn as_tensor_shape_test_too_large() {
+        let shape = vec![1; MAX_TENSOR_DIMS + 1];
+        let err = shape.as_tensor_shape().unwrap_err();
+        assert!(matches!(
+            err.into_inner().current_context(),
+            crate::ErrorKind::TooManyDimensions { got, .. } if *got == MAX_TENSOR_DIMS + 1
+        ));
+    }
 }
 
 #[cfg(test)]
@@ -651,8 +803,10 @@ where
     T::H: HalideType,
 {
     /// Try to create a ref tensor from any array-like type
-    pub fn borrowed(shape: impl AsTensorShape, input: impl AsRef<[T::H]>) -> Self {
-        let shape = shape.as_tensor_shape();
+    ///
+    /// Returns an error if the shape has more than [`MAX_TENSOR_DIMS`] dimensions
+    pub fn try_borrowed(shape: impl AsTensorShape, input: impl AsRef<[T::H]>) -> Result<Self> {
+        let shape = shape.as_tensor_shape()?;
         let input = input.as_ref();
         let tensor = unsafe {
             Tensor_createWith(
@@ -664,15 +818,28 @@ where
             )
         };
         debug_assert!(!tensor.is_null());
-        Self {
+        Ok(Self {
             tensor,
             __marker: PhantomData,
-        }
+        })
+    }
+
+    /// Create a ref tensor from any array-like type
+    ///
+    /// # Panics
+    /// Panics if the shape has more than [`MAX_TENSOR_DIMS`] dimensions
+    pub fn borrowed(shape: impl AsTensorShape, input: impl AsRef<[T::H]>) -> Self {
+        Self::try_borrowed(shape, input).expect("Failed to create borrowed tensor")
     }
 
     /// Try to create a mutable ref tensor from any array-like type
-    pub fn borrowed_mut(shape: impl AsTensorShape, mut input: impl AsMut<[T::H]>) -> Self {
-        let shape = shape.as_tensor_shape();
+    ///
+    /// Returns an error if the shape has more than [`MAX_TENSOR_DIMS`] dimensions
+    pub fn try_borrowed_mut(
+        shape: impl AsTensorShape,
+        mut input: impl AsMut<[T::H]>,
+    ) -> Result<Self> {
+        let shape = shape.as_tensor_shape()?;
         let input = input.as_mut();
         let tensor = unsafe {
             Tensor_createWith(
@@ -684,10 +851,18 @@ where
             )
         };
         debug_assert!(!tensor.is_null());
-        Self {
+        Ok(Self {
             tensor,
             __marker: PhantomData,
-        }
+        })
+    }
+
+    /// Create a mutable ref tensor from any array-like type
+    ///
+    /// # Panics
+    /// Panics if the shape has more than [`MAX_TENSOR_DIMS`] dimensions
+    pub fn borrowed_mut(shape: impl AsTensorShape, input: impl AsMut<[T::H]>) -> Self {
+        Self::try_borrowed_mut(shape, input).expect("Failed to create borrowed tensor")
     }
 }
 
@@ -708,3 +883,23 @@ fn test_tensor_borrow_mut() {
     tensor.host_mut().fill(1);
     assert_eq!(data, &[1, 1, 1, 1, 1, 1]);
 }
+
+#[test]
+fn test_tensor_rank_6() {
+    let shape = [1, 2, 1, 3, 1, 2];
+    let data = vec![0f32; 12];
+    let tensor = Tensor::<Ref<Host<f32>>>::borrowed(&shape, &data);
+    assert_eq!(tensor.dimensions(), 6);
+    assert_eq!(tensor.shape().as_ref(), shape);
+    let owned = Tensor::<Host<f32>>::new(shape, DimensionType::Caffe);
+    assert_eq!(owned.shape().as_ref(), shape);
+    assert_eq!(owned.element_size(), 12);
+}
+
+#[test]
+fn test_tensor_too_many_dims() {
+    let shape = [1; MAX_TENSOR_DIMS + 1];
+    let data = vec![0f32; 1];
+    assert!(Tensor::<Ref<Host<f32>>>::try_borrowed(shape, &data).is_err());
+    assert!(Tensor::<Host<f32>>::try_new(shape, DimensionType::Caffe).is_err());
+}
@@ -0,0 +1,131 @@
+use super::*;
+use crate::ForwardType;
+
+/// Backend specific information about the memory of a tensor
+///
+/// Queried with [`Tensor::device_info`]. [`CpuDeviceInfo`] is provided for the CPU backend, for
+/// other backends implement this trait for the struct the backend fills in
+/// `Backend::onGetTensorInfo`.
+///
+/// # Safety
+/// If [`DeviceInfo::query`] isn't overridden `Self` must have the same layout as the struct the
+/// backend writes for [`DeviceInfo::BACKEND`].
+pub unsafe trait DeviceInfo: Sized {
+    /// The backend the information is for
+    const BACKEND: ForwardType;
+
+    /// Query the information for a tensor
+    fn query(tensor: &RawTensor) -> Result<Self> {
+        let mut info = core::mem::MaybeUninit::<Self>::uninit();
+        let ret = unsafe {
+            Tensor_getDeviceInfo(
+                tensor.inner,
+                info.as_mut_ptr().cast(),
+                Self::BACKEND.to_raw(),
+            )
+        };
+        ensure!(
+            ret != 0,
+            ErrorKind::NotSupported;
+            format!("No device info for backend {}", Self::BACKEND.to_str())
+        );
+        // SAFETY: The backend filled the info
+        Ok(unsafe { info.assume_init() })
+    }
+}
+
+/// Memory of a tensor on the CPU backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuDeviceInfo {
+    /// Pointer to the data of the tensor
+    pub ptr: *mut c_void,
+    /// Size of the data in bytes
+    pub size: usize,
+}
+
+/// Check that the tensor isn't allocated on a backend other than the CPU, tensors without a
+/// backend (e.g. created with [`Tensor::new`]) use host memory
+fn ensure_cpu_tensor(tensor: *const mnn_sys::Tensor) -> Result<()> {
+    let backend = unsafe { Tensor_getBackendType(tensor) };
+    ensure!(
+        backend < 0 || ForwardType::from_raw(backend) == Some(ForwardType::CPU),
+        ErrorKind::NotSupported;
+        format!("The tensor belongs to the backend {}", ForwardType::from_raw(backend).map_or("unknown", ForwardType::to_str))
+    );
+    Ok(())
+}
+
+// SAFETY: `query` is overridden
+unsafe impl DeviceInfo for CpuDeviceInfo {
+    const BACKEND: ForwardType = ForwardType::CPU;
+
+    /// The CPU backend doesn't describe its tensors in `onGetTensorInfo`, the host memory is the
+    /// device memory
+    fn query(tensor: &RawTensor) -> Result<Self> {
+        ensure_cpu_tensor(tensor.inner)?;
+        let ptr = unsafe { Tensor_host_mut(tensor.inner) };
+        ensure!(!ptr.is_null(), ErrorKind::TensorError; "Tensor has no host memory");
+        Ok(Self {
+            ptr,
+            size: tensor.size(),
+        })
+    }
+}
+
+impl<T: DeviceTensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Query backend specific information about the memory of the tensor
+    ///
+    /// Returns an error if the tensor doesn't belong to the backend of `I`.
+    pub fn device_info<I: DeviceInfo>(&self) -> Result<I> {
+        I::query(&RawTensor::from_ptr(self.tensor))
+    }
+}
+
+impl<T: DeviceTensorType + MutableTensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Use externally allocated memory of `backend` for the tensor
+    ///
+    /// For [`ForwardType::CPU`] `ptr` is used as the host memory of the tensor, for other
+    /// backends it's the backend specific memory handle (e.g. a `cl_mem` for OpenCL).
+    ///
+    /// MNN only records the handle for the GPU backends, the CPU backend reads the host memory.
+    /// So for the CPU the host memory is set directly, which is only allowed for tensors which
+    /// don't have memory yet (e.g. created with [`Tensor::new`]) and don't belong to another
+    /// backend. Others return [`ErrorKind::NotSupported`], replacing the memory of an allocated
+    /// tensor would leak it or leave the backend with a dangling pointer.
+    ///
+    /// # Safety
+    /// `ptr` has to point to memory of `backend` which is valid for the size of the tensor and
+    /// outlives every use of the tensor, the memory isn't freed by the tensor.
+    pub unsafe fn set_device_ptr(
+        &mut self,
+        ptr: *const c_void,
+        backend: ForwardType,
+    ) -> Result<()> {
+        ensure!(!ptr.is_null(), ErrorKind::InvalidValue; "Device pointer is null");
+        if backend == ForwardType::CPU {
+            ensure_cpu_tensor(self.tensor)?;
+            let buffer = unsafe { Tensor_buffer_mut(self.tensor) };
+            ensure!(!buffer.is_null(), ErrorKind::TensorError; "Tensor has no buffer");
+            ensure!(
+                unsafe { (*buffer).host }.is_null(),
+                ErrorKind::NotSupported;
+                "The tensor is already allocated, only tensors without memory can use external memory"
+            );
+            unsafe { (*buffer).host = ptr.cast_mut().cast() };
+            return Ok(());
+        }
+        let ret = unsafe { Tensor_setDevicePtr(self.tensor, ptr, backend.to_raw()) };
+        ensure!(
+            ret != 0,
+            ErrorKind::NotSupported;
+            format!("Failed to set the device pointer for backend {}", backend.to_str())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+use super::*;
+
+/// The batch, channel and spatial sizes of a tensor independent of its layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Dims {
+    batch: usize,
+    channels: usize,
+    area: usize,
+    /// The spatial dimensions in order, e.g. `[H, W]`
+    spatial: Vec<i32>,
+}
+
+impl Dims {
+    /// Split `shape` into the batch, channel and spatial dimensions of `layout`
+    ///
+    /// Shapes are `[N, C, ...]` for NCHW and NC4HW4 and `[N, ..., C]` for NHWC.
+    fn new(shape: &[i32], layout: DimensionType) -> Self {
+        let shape = shape.iter().map(|&dim| dim.max(0)).collect::<Vec<_>>();
+        let (batch, channels, spatial) = match (layout, shape.as_slice()) {
+            (_, []) => (1, 1, Vec::new()),
+            (_, [channels]) => (1, *channels, Vec::new()),
+            (DimensionType::TensorFlow, [batch, spatial @ .., channels]) => {
+                (*batch, *channels, spatial.to_vec())
+            }
+            (_, [batch, channels, spatial @ ..]) => (*batch, *channels, spatial.to_vec()),
+        };
+        Self {
+            batch: batch as usize,
+            channels: channels as usize,
+            area: spatial.iter().map(|&dim| dim as usize).product(),
+            spatial,
+        }
+    }
+
+    /// The shape of the tensor in `layout`
+    fn shape(&self, rank: usize, layout: DimensionType) -> Vec<i32> {
+        let (batch, channels) = (self.batch as i32, self.channels as i32);
+        match (rank, layout) {
+            (0, _) => Vec::new(),
+            (1, _) => vec![channels],
+            (_, DimensionType::TensorFlow) => core::iter::once(batch)
+                .chain(self.spatial.iter().copied())
+                .chain(core::iter::once(channels))
+                .collect(),
+            _ => [batch, channels]
+                .into_iter()
+                .chain(self.spatial.iter().copied())
+                .collect(),
+        }
+    }
+
+    /// Offset of an element in `layout` in elements
+    fn offset(&self, layout: DimensionType, n: usize, c: usize, s: usize) -> usize {
+        match layout {
+            DimensionType::Caffe => (n * self.channels + c) * self.area + s,
+            DimensionType::TensorFlow => (n * self.area + s) * self.channels + c,
+            DimensionType::CaffeC4 => {
+                ((n * self.channels.div_ceil(4) + c / 4) * self.area + s) * 4 + c % 4
+            }
+        }
+    }
+}
+
+/// Rearrange the elements of `src` in `from` into `dst` in `to`
+///
+/// `dst` has to be large enough for `to`, the channel padding of NC4HW4 is filled with zeros.
+fn convert(
+    src: &[u8],
+    from: DimensionType,
+    dst: &mut [u8],
+    to: DimensionType,
+    dims: &Dims,
+    element: usize,
+) {
+    dst.fill(0);
+    for n in 0..dims.batch {
+        for c in 0..dims.channels {
+            for s in 0..dims.area {
+                let src_offset = dims.offset(from, n, c, s) * element;
+                let dst_offset = dims.offset(to, n, c, s) * element;
+                dst[dst_offset..dst_offset + element]
+                    .copy_from_slice(&src[src_offset..src_offset + element]);
+            }
+        }
+    }
+}
+
+impl RawTensor<'_> {
+    /// Returns the memory layout of the tensor
+    ///
+    /// Unlike [`RawTensor::get_dimension_type`] this reports [`DimensionType::NC4HW4`] for
+    /// tensors with packed channels.
+    pub fn layout(&self) -> DimensionType {
+        debug_assert!(!self.inner.is_null());
+        From::from(unsafe { Tensor_getDataFormat(self.inner) })
+    }
+
+    /// Try to copy the host tensor into a new host tensor with `layout`
+    ///
+    /// The shape of the new tensor is in the order of `layout`, e.g. `[N, H, W, C]` for NHWC.
+    /// The returned tensor is owned by the caller and has to be destroyed with
+    /// [`RawTensor::destroy`].
+    pub fn try_convert_layout(&self, layout: DimensionType) -> Result<RawTensor<'static>> {
+        let src = unsafe { Tensor_host(self.inner) };
+        ensure!(
+            !src.is_null(),
+            ErrorKind::TensorError;
+            "Only host tensors can be converted, copy device tensors to the host first"
+        );
+        ensure!(!self.is_dynamic_unsized(), ErrorKind::DynamicTensorError);
+        let from = self.layout();
+        let shape = self.shape();
+        let dims = Dims::new(shape.as_ref(), from);
+        let dst_shape = dims.shape(shape.len(), layout);
+        let halide_type = self.get_type();
+        let tensor = unsafe {
+            Tensor_createWith(
+                dst_shape.as_ptr(),
+                dst_shape.len(),
+                halide_type,
+                core::ptr::null_mut(),
+                layout.to_mnn_sys(),
+            )
+        };
+        ensure!(!tensor.is_null(), ErrorKind::TensorError; "Failed to create the converted tensor", format!("Shape: {dst_shape:?}"));
+        let mut dst = RawTensor::from_ptr(tensor);
+        let element = (halide_type.bits as usize).div_ceil(8) * halide_type.lanes as usize;
+        let src = unsafe { core::slice::from_raw_parts(src.cast::<u8>(), self.size()) };
+        convert(
+            src,
+            from,
+            unsafe { dst.unchecked_host_bytes() },
+            layout,
+            &dims,
+            element,
+        );
+        Ok(dst)
+    }
+
+    /// Copy the host tensor into a new host tensor with `layout`
+    pub fn convert_layout(&self, layout: DimensionType) -> RawTensor<'static> {
+        self.try_convert_layout(layout)
+            .expect("Failed to convert tensor layout")
+    }
+}
+
+impl<T: TensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Returns the memory layout of the tensor
+    ///
+    /// Unlike [`Tensor::get_dimension_type`] this reports [`DimensionType::NC4HW4`] for tensors
+    /// with packed channels.
+    pub fn layout(&self) -> DimensionType {
+        RawTensor::from_ptr(self.tensor).layout()
+    }
+}
+
+impl<T: HostTensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Try to copy the tensor into a new host tensor with `layout`
+    ///
+    /// NCHW and NC4HW4 tensors have the shape `[N, C, ...]` and NHWC tensors `[N, ..., C]`,
+    /// the channels of NC4HW4 are padded to a multiple of 4 with zeros.
+    ///
+    /// ```
+    /// # use mnn::*;
+    /// # fn main() -> Result<()> {
+    /// let mut nchw = Tensor::<Host<f32>>::new([1, 3, 2, 2], DimensionType::NCHW);
+    /// nchw.fill(1.0);
+    /// let nhwc = nchw.try_convert_layout(DimensionType::NHWC)?;
+    /// assert_eq!(nhwc.shape().as_ref(), [1, 2, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_convert_layout(&self, layout: DimensionType) -> Result<Tensor<Host<T::H>>> {
+        ensure!(
+            self.is_type_of::<T::H>(),
+            ErrorKind::HalideTypeMismatch {
+                got: std::any::type_name::<T::H>(),
+            }
+        );
+        let tensor = RawTensor::from_ptr(self.tensor).try_convert_layout(layout)?;
+        Ok(unsafe { Tensor::from_ptr(tensor.inner) })
+    }
+
+    /// Copy the tensor into a new host tensor with `layout`
+    pub fn convert_layout(&self, layout: DimensionType) -> Tensor<Host<T::H>> {
+        self.try_convert_layout(layout)
+            .expect("Failed to convert tensor layout")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dims() {
+        let nchw = Dims::new(&[2, 5, 3, 4], DimensionType::NCHW);
+        let nhwc = Dims::new(&[2, 3, 4, 5], DimensionType::NHWC);
+        assert_eq!(nchw, nhwc);
+        assert_eq!(nchw.area, 12);
+        assert_eq!(nchw.shape(4, DimensionType::NHWC), [2, 3, 4, 5]);
+        assert_eq!(nhwc.shape(4, DimensionType::NC4HW4), [2, 5, 3, 4]);
+    }
+
+    #[test]
+    fn test_convert_offsets() {
+        // One batch, 5 channels and 2 pixels, the value is the channel * 10 + pixel
+        let dims = Dims::new(&[1, 5, 2], DimensionType::NCHW);
+        let nchw = [0u8, 1, 10, 11, 20, 21, 30, 31, 40, 41];
+        let mut nhwc = [0; 10];
+        convert(
+            &nchw,
+            DimensionType::NCHW,
+            &mut nhwc,
+            DimensionType::NHWC,
+            &dims,
+            1,
+        );
+        assert_eq!(nhwc, [0, 10, 20, 30, 40, 1, 11, 21, 31, 41]);
+        let mut nc4hw4 = [u8::MAX; 16];
+        convert(
+            &nhwc,
+            DimensionType::NHWC,
+            &mut nc4hw4,
+            DimensionType::NC4HW4,
+            &dims,
+            1,
+        );
+        assert_eq!(
+            nc4hw4,
+            [0, 10, 20, 30, 1, 11, 21, 31, 40, 0, 0, 0, 41, 0, 0, 0]
+        );
+    }
+}
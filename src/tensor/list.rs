@@ -16,7 +16,9 @@ impl core::fmt::Debug for TensorList<'_> {
 
 impl Drop for TensorList<'_> {
     fn drop(&mut self) {
-        unsafe { mnn_sys::destroyTensorInfoArray(self.inner.cast_mut()) }
+        if !self.inner.is_null() {
+            unsafe { mnn_sys::destroyTensorInfoArray(self.inner.cast_mut()) }
+        }
     }
 }
 
@@ -30,6 +32,9 @@ impl<'t> TensorList<'t> {
 
     /// Returns the size of the tensor list
     pub fn size(&self) -> usize {
+        if self.inner.is_null() {
+            return 0;
+        }
         unsafe { (*self.inner).size }
     }
 
@@ -95,23 +100,35 @@ impl core::fmt::Debug for TensorInfo<'_, '_> {
         let tensor = self.raw_tensor();
         let shape = tensor.shape();
         f.debug_struct("TensorInfo")
-            .field("name", &self.name())
+            .field("name", &self.name_lossy())
             .field("tensor", &shape)
             .finish()
     }
 }
 
 impl<'t, 'tl> TensorInfo<'t, 'tl> {
+    /// Try to get the name of the tensor, fails if the name is not valid utf-8
+    pub fn try_name(&self) -> Result<&'tl str> {
+        debug_assert!(!self.tensor_info.is_null());
+        let name = unsafe { (*self.tensor_info).name.to_cstr() };
+        name.to_str()
+            .change_context(ErrorKind::AsciiError)
+            .map_err(|e| MNNError::from(e).attach_printable(format!("Tensor name {name:?}")))
+    }
+
+    /// Get the name of the tensor
     pub fn name(&self) -> &'tl str {
+        self.try_name().expect("Tensor name is not utf-8")
+    }
+
+    fn name_lossy(&self) -> std::borrow::Cow<'tl, str> {
         debug_assert!(!self.tensor_info.is_null());
-        unsafe { (*self.tensor_info).name.to_cstr() }
-            .to_str()
-            .expect("Tensor name is not utf-8")
+        unsafe { (*self.tensor_info).name.to_cstr() }.to_string_lossy()
     }
 
     pub fn tensor<H: HalideType>(&self) -> Result<Tensor<RefMut<'t, Device<H>>>> {
         debug_assert!(!self.tensor_info.is_null());
-        unsafe { debug_assert!(!(*self.tensor_info).tensor.is_null()) };
+        ensure!(unsafe { !(*self.tensor_info).tensor.is_null() }, ErrorKind::TensorError; format!("Tensor \"{}\" is null", self.name_lossy()));
         let tensor = unsafe { Tensor::from_ptr((*self.tensor_info).tensor.cast()) };
         let shape = tensor.shape();
         ensure!(!shape.as_ref().contains(&-1), ErrorKind::DynamicTensorError);
@@ -128,7 +145,7 @@ impl<'t, 'tl> TensorInfo<'t, 'tl> {
     /// The shape is not checked so it's marked unsafe since futher calls to interpreter might be **unsafe** with this
     pub unsafe fn tensor_unresized<H: HalideType>(&self) -> Result<Tensor<RefMut<'t, Device<H>>>> {
         debug_assert!(!self.tensor_info.is_null());
-        unsafe { debug_assert!(!(*self.tensor_info).tensor.is_null()) };
+        ensure!(unsafe { !(*self.tensor_info).tensor.is_null() }, ErrorKind::TensorError; format!("Tensor \"{}\" is null", self.name_lossy()));
         let tensor = unsafe { Tensor::from_ptr((*self.tensor_info).tensor.cast()) };
         ensure!(
             tensor.is_type_of::<H>(),
@@ -0,0 +1,205 @@
+use super::*;
+
+/// Read access to the data of a device tensor mapped into host memory
+///
+/// Created with [`Tensor::map`], the tensor is unmapped when the guard is dropped.
+pub struct MappedTensor<'t, H: HalideType> {
+    mapping: Mapping,
+    __marker: PhantomData<&'t [H]>,
+}
+
+/// Write access to the data of a device tensor mapped into host memory
+///
+/// Created with [`Tensor::map_mut`], the data is written back to the device when the guard is
+/// dropped. The mapped memory starts out zeroed, the previous data of the tensor isn't readable
+/// through it.
+pub struct MappedTensorMut<'t, H: HalideType> {
+    mapping: Mapping,
+    __marker: PhantomData<&'t mut [H]>,
+}
+
+/// A mapped pointer which is unmapped on drop
+struct Mapping {
+    tensor: *mut mnn_sys::Tensor,
+    map_type: MapType,
+    dm_type: DimensionType,
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl Mapping {
+    fn new<H: HalideType>(
+        tensor: *mut mnn_sys::Tensor,
+        map_type: MapType,
+        dm_type: DimensionType,
+    ) -> Result<Self> {
+        let shape = TensorShape::of(tensor)?;
+        ensure!(
+            !shape.as_ref().iter().any(|&dim| dim < 0),
+            ErrorKind::DynamicTensorError;
+            format!("Can't map a tensor with shape {shape:?}")
+        );
+        // MNN maps NC4HW4 tensors as they are stored, the padded length below is only known to
+        // be mapped if the tensor is actually stored in NC4HW4
+        if dm_type == DimensionType::CaffeC4 {
+            let layout = DimensionType::from(unsafe { Tensor_getDataFormat(tensor) });
+            ensure!(
+                layout == DimensionType::CaffeC4,
+                ErrorKind::NotSupported;
+                "Only tensors stored in NC4HW4 can be mapped as NC4HW4",
+                format!("The tensor is stored in {layout:?}")
+            );
+        }
+        let elements = shape
+            .as_ref()
+            .iter()
+            .map(|&dim| dim as usize)
+            .product::<usize>();
+        let len = match dm_type {
+            // The channels are padded to a multiple of 4 in NC4HW4
+            DimensionType::CaffeC4 => match unsafe { Tensor_channel(tensor) }.max(0) as usize {
+                0 => elements,
+                channels => elements / channels * channels.div_ceil(4) * 4,
+            },
+            _ => elements,
+        };
+        let ptr = unsafe { Tensor_map(tensor, map_type, dm_type.to_mnn_sys()) };
+        ensure!(
+            !ptr.is_null(),
+            ErrorKind::TensorError;
+            "Failed to map the tensor",
+            format!("Map type: {map_type:?}, dimension type: {dm_type:?}")
+        );
+        // Unmapped on drop if the checks below fail
+        let mapping = Self {
+            tensor,
+            map_type,
+            dm_type,
+            ptr,
+            len,
+        };
+        ensure!(
+            ptr.cast::<H>().is_aligned(),
+            ErrorKind::TensorError;
+            "The mapped memory isn't aligned for the element type",
+            format!("Address: {ptr:p}, element type: {}", std::any::type_name::<H>())
+        );
+        Ok(mapping)
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe {
+            Tensor_unmap(
+                self.tensor,
+                self.map_type,
+                self.dm_type.to_mnn_sys(),
+                self.ptr,
+            )
+        }
+    }
+}
+
+impl<H: HalideType> MappedTensor<'_, H> {
+    /// The dimension type of the mapped data
+    pub fn dimension_type(&self) -> DimensionType {
+        self.mapping.dm_type
+    }
+}
+
+impl<H: HalideType> MappedTensorMut<'_, H> {
+    /// The dimension type of the mapped data
+    pub fn dimension_type(&self) -> DimensionType {
+        self.mapping.dm_type
+    }
+}
+
+impl<H: HalideType> core::ops::Deref for MappedTensor<'_, H> {
+    type Target = [H];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::slice::from_raw_parts(self.mapping.ptr.cast(), self.mapping.len) }
+    }
+}
+
+impl<H: HalideType> core::ops::Deref for MappedTensorMut<'_, H> {
+    type Target = [H];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::slice::from_raw_parts(self.mapping.ptr.cast(), self.mapping.len) }
+    }
+}
+
+impl<H: HalideType> core::ops::DerefMut for MappedTensorMut<'_, H> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { core::slice::from_raw_parts_mut(self.mapping.ptr.cast(), self.mapping.len) }
+    }
+}
+
+impl<H: HalideType + core::fmt::Debug> core::fmt::Debug for MappedTensor<'_, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MappedTensor")
+            .field("dimension_type", &self.mapping.dm_type)
+            .field("data", &&**self)
+            .finish()
+    }
+}
+
+impl<H: HalideType + core::fmt::Debug> core::fmt::Debug for MappedTensorMut<'_, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MappedTensorMut")
+            .field("dimension_type", &self.mapping.dm_type)
+            .field("data", &&**self)
+            .finish()
+    }
+}
+
+impl<T: DeviceTensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Try to map the data of the device tensor into host memory for reading
+    ///
+    /// Unlike [`Tensor::create_host_tensor_from_device`] this doesn't allocate a host tensor if
+    /// the backend can map its memory directly. The data is laid out as `dm_type`, in
+    /// [`DimensionType::NC4HW4`] the channels are padded to a multiple of 4. Only tensors which
+    /// are stored in NC4HW4 (see [`Tensor::layout`]) can be mapped as NC4HW4, others return
+    /// [`ErrorKind::NotSupported`].
+    pub fn try_map(&self, dm_type: DimensionType) -> Result<MappedTensor<'_, T::H>> {
+        Ok(MappedTensor {
+            mapping: Mapping::new::<T::H>(self.tensor, MapType::MAP_TENSOR_READ, dm_type)?,
+            __marker: PhantomData,
+        })
+    }
+
+    /// Map the data of the device tensor into host memory for reading
+    pub fn map(&self, dm_type: DimensionType) -> MappedTensor<'_, T::H> {
+        self.try_map(dm_type).expect("Failed to map tensor")
+    }
+}
+
+impl<T: DeviceTensorType + MutableTensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Try to map the data of the device tensor into host memory for writing
+    ///
+    /// The data is uploaded to the device when the returned guard is dropped, see
+    /// [`Tensor::try_map`] for the layout. Backends may hand out a staging buffer without the
+    /// data of the tensor, so the mapped memory is zeroed first.
+    pub fn try_map_mut(&mut self, dm_type: DimensionType) -> Result<MappedTensorMut<'_, T::H>> {
+        let mapping = Mapping::new::<T::H>(self.tensor, MapType::MAP_TENSOR_WRITE, dm_type)?;
+        // SAFETY: Halide types are plain numbers, all zero bits is a valid value
+        unsafe { core::ptr::write_bytes(mapping.ptr.cast::<T::H>(), 0, mapping.len) };
+        Ok(MappedTensorMut {
+            mapping,
+            __marker: PhantomData,
+        })
+    }
+
+    /// Map the data of the device tensor into host memory for writing
+    pub fn map_mut(&mut self, dm_type: DimensionType) -> MappedTensorMut<'_, T::H> {
+        self.try_map_mut(dm_type).expect("Failed to map tensor")
+    }
+}
@@ -19,15 +19,50 @@ pub struct RawTensor<'r> {
 
 impl RawTensor<'_> {
     /// Creates a new host tensor from the device tensor
-    pub fn create_host_tensor_from_device(&self, copy_data: bool) -> RawTensor<'static> {
+    pub fn try_create_host_tensor_from_device(
+        &self,
+        copy_data: bool,
+    ) -> Result<RawTensor<'static>> {
         let tensor =
             unsafe { mnn_sys::Tensor_createHostTensorFromDevice(self.inner, copy_data as i32) };
-        // crate::ensure!(!tensor.is_null(), ErrorKind::TensorError);
-        assert!(!tensor.is_null());
-        RawTensor {
+        crate::ensure!(!tensor.is_null(), ErrorKind::TensorError; "Failed to create host tensor from device tensor", format!("Shape: {:?}", self.shape()));
+        Ok(RawTensor {
             inner: tensor,
             __marker: PhantomData,
-        }
+        })
+    }
+
+    /// Creates a new host tensor from the device tensor
+    pub fn create_host_tensor_from_device(&self, copy_data: bool) -> RawTensor<'static> {
+        self.try_create_host_tensor_from_device(copy_data)
+            .expect("Failed to create host tensor from device tensor")
+    }
+
+    /// Creates a new host tensor with the shape and type of the tensor in NCHW or NHWC
+    ///
+    /// Unlike [`RawTensor::try_create_host_tensor_from_device`] the channels of NC4HW4 tensors
+    /// aren't packed, [`RawTensor::copy_from_host_tensor`] and [`RawTensor::copy_to_host_tensor`]
+    /// convert between the layouts.
+    pub(crate) fn try_create_plain_host_tensor(&self) -> Result<RawTensor<'static>> {
+        let dm_type = match self.layout() {
+            crate::DimensionType::TensorFlow => crate::DimensionType::TensorFlow,
+            _ => crate::DimensionType::Caffe,
+        };
+        let shape = self.shape();
+        let tensor = unsafe {
+            mnn_sys::Tensor_createWith(
+                shape.shape.as_ptr(),
+                shape.size,
+                self.get_type(),
+                core::ptr::null_mut(),
+                dm_type.to_mnn_sys(),
+            )
+        };
+        crate::ensure!(!tensor.is_null(), ErrorKind::TensorError; "Failed to create host tensor", format!("Shape: {:?}", shape));
+        Ok(RawTensor {
+            inner: tensor,
+            __marker: PhantomData,
+        })
     }
 
     /// Copies the data from a host tensor to the self tensor
@@ -44,9 +79,27 @@ impl RawTensor<'_> {
         Ok(())
     }
 
+    /// Try to get the shape of the tensor
+    ///
+    /// Returns an error if the tensor has more than [`MAX_TENSOR_DIMS`](crate::MAX_TENSOR_DIMS)
+    /// dimensions
+    pub fn try_shape(&self) -> Result<crate::TensorShape> {
+        crate::TensorShape::of(self.inner)
+    }
+
     /// Returns the shape of the tensor
     pub fn shape(&self) -> crate::TensorShape {
-        unsafe { mnn_sys::Tensor_shape(self.inner) }.into()
+        self.try_shape().expect("Failed to get the tensor shape")
+    }
+
+    /// Returns the data type of the tensor
+    pub fn get_type(&self) -> mnn_sys::halide_type_t {
+        unsafe { mnn_sys::Tensor_getType(self.inner) }
+    }
+
+    /// Check if the tensor is of the specified data type
+    pub fn is_type_of<H: HalideType>(&self) -> bool {
+        unsafe { mnn_sys::Tensor_isTypeOf(self.inner, mnn_sys::halide_type_of::<H>()) }
     }
 
     /// Returns the dimension type of the tensor
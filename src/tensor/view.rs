@@ -0,0 +1,372 @@
+use super::*;
+use core::ops::{Bound, RangeBounds};
+
+/// A strided view into the data of a host tensor
+///
+/// Created with [`Tensor::view`], every axis has a length and a stride in elements. Selecting
+/// and slicing axes only changes the lengths and the offset of the view, the data is copied
+/// with [`TensorView::to_contiguous`].
+///
+/// ```
+/// # use mnn::*;
+/// # fn main() -> Result<()> {
+/// let mut tensor = Tensor::<Host<f32>>::new([2, 3, 4, 4], DimensionType::NCHW);
+/// tensor.fill(1.0);
+/// // The second batch item cropped to the center 2x2 window
+/// let crop = tensor.view().select(0, 1)?.slice(1, 1..3)?.slice(2, 1..3)?;
+/// assert_eq!(crop.shape(), [3, 2, 2]);
+/// let packed = crop.to_contiguous()?;
+/// assert_eq!(packed.host().len(), 12);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TensorView<'t, H: HalideType> {
+    data: &'t [H],
+    offset: usize,
+    shape: Vec<usize>,
+    strides: Vec<usize>,
+    dm_type: DimensionType,
+}
+
+impl<H: HalideType + core::fmt::Debug> core::fmt::Debug for TensorView<'_, H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TensorView")
+            .field("shape", &self.shape)
+            .field("strides", &self.strides)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'t, H: HalideType> TensorView<'t, H> {
+    /// The length of every axis
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// The stride of every axis in elements
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    /// The number of axes
+    pub fn dimensions(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// The number of elements in the view
+    pub fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    /// Returns true if the view has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the elements are packed in row-major order without gaps
+    pub fn is_contiguous(&self) -> bool {
+        let mut expected = 1;
+        for (&len, &stride) in self.shape.iter().zip(&self.strides).rev() {
+            if len != 1 && stride != expected {
+                return false;
+            }
+            expected *= len;
+        }
+        true
+    }
+
+    /// Get the element at `index`, returns `None` if the index is out of bounds
+    pub fn get(&self, index: &[usize]) -> Option<&'t H> {
+        if index.len() != self.shape.len() || index.iter().zip(&self.shape).any(|(i, l)| i >= l) {
+            return None;
+        }
+        let offset = index
+            .iter()
+            .zip(&self.strides)
+            .map(|(i, stride)| i * stride)
+            .sum::<usize>();
+        self.data.get(self.offset + offset)
+    }
+
+    /// Take the item `index` along `axis`, the axis is removed from the view
+    pub fn select(mut self, axis: usize, index: usize) -> Result<Self> {
+        self.check_axis(axis)?;
+        ensure!(
+            index < self.shape[axis],
+            ErrorKind::InvalidValue;
+            format!("Index {index} is out of bounds for axis {axis} with length {}", self.shape[axis])
+        );
+        self.offset += index * self.strides[axis];
+        self.shape.remove(axis);
+        self.strides.remove(axis);
+        Ok(self)
+    }
+
+    /// Restrict `axis` to `range`
+    pub fn slice(mut self, axis: usize, range: impl RangeBounds<usize>) -> Result<Self> {
+        self.check_axis(axis)?;
+        let len = self.shape[axis];
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        ensure!(
+            start <= end && end <= len,
+            ErrorKind::InvalidValue;
+            format!("Range {start}..{end} is out of bounds for axis {axis} with length {len}")
+        );
+        self.offset += start * self.strides[axis];
+        self.shape[axis] = end - start;
+        Ok(self)
+    }
+
+    /// Iterate over the elements in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = &'t H> + '_ {
+        let mut index = vec![0; self.shape.len()];
+        let mut remaining = self.len();
+        core::iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            remaining -= 1;
+            let offset = index
+                .iter()
+                .zip(&self.strides)
+                .map(|(i, stride)| i * stride)
+                .sum::<usize>();
+            // Advance the index like an odometer, the last axis is the fastest
+            for (i, &len) in index.iter_mut().zip(&self.shape).rev() {
+                *i += 1;
+                if *i < len {
+                    break;
+                }
+                *i = 0;
+            }
+            Some(&self.data[self.offset + offset])
+        })
+    }
+
+    /// The elements as a slice if the view is contiguous
+    pub fn as_slice(&self) -> Option<&'t [H]> {
+        self.is_contiguous()
+            .then(|| &self.data[self.offset..self.offset + self.len()])
+    }
+
+    /// Copy the elements into a vector in row-major order
+    pub fn to_vec(&self) -> Vec<H>
+    where
+        H: Copy,
+    {
+        match self.as_slice() {
+            Some(slice) => slice.to_vec(),
+            None => self.iter().copied().collect(),
+        }
+    }
+
+    /// Copy the elements into a new packed host tensor with the shape of the view
+    pub fn to_contiguous(&self) -> Result<Tensor<Host<H>>>
+    where
+        H: Copy,
+    {
+        let shape = self.shape.iter().map(|&len| len as i32).collect::<Vec<_>>();
+        let mut tensor = Tensor::<Host<H>>::try_new(shape, self.dm_type)?;
+        let host = tensor.try_host_mut()?;
+        match self.as_slice() {
+            Some(slice) => host.copy_from_slice(slice),
+            None => host
+                .iter_mut()
+                .zip(self.iter())
+                .for_each(|(dst, src)| *dst = *src),
+        }
+        Ok(tensor)
+    }
+
+    fn check_axis(&self, axis: usize) -> Result<()> {
+        ensure!(
+            axis < self.shape.len(),
+            ErrorKind::InvalidValue;
+            format!("Axis {axis} is out of bounds for a view with {} axes", self.shape.len())
+        );
+        Ok(())
+    }
+}
+
+impl<T: HostTensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Try to create a strided view of the tensor
+    ///
+    /// The lengths and strides of the axes are read from the tensor, see [`Tensor::length`] and
+    /// [`Tensor::stride`]. Returns [`ErrorKind::InvalidValue`] if the strides reach past the
+    /// [`size`](Tensor::size) of the tensor. NC4HW4 tensors can't be viewed, convert them with
+    /// [`Tensor::convert_layout`] first.
+    pub fn try_view(&self) -> Result<TensorView<'_, T::H>> {
+        ensure!(
+            self.is_type_of::<T::H>(),
+            ErrorKind::HalideTypeMismatch {
+                got: std::any::type_name::<T::H>(),
+            }
+        );
+        let dm_type = self.layout();
+        ensure!(
+            dm_type != DimensionType::CaffeC4,
+            ErrorKind::NotSupported;
+            "Views of NC4HW4 tensors are not supported, see Tensor::convert_layout"
+        );
+        let dimensions = self.dimensions();
+        let shape = (0..dimensions)
+            .map(|axis| self.length(axis).max(0) as usize)
+            .collect::<Vec<_>>();
+        let strides = (0..dimensions)
+            .map(|axis| self.stride(axis).max(0) as usize)
+            .collect::<Vec<_>>();
+        let last = shape
+            .iter()
+            .zip(&strides)
+            .map(|(&len, &stride)| len.saturating_sub(1) * stride)
+            .sum::<usize>();
+        let reachable = if shape.contains(&0) { 0 } else { last + 1 };
+        // The size is computed from the lengths, strides changed with `Tensor::set_stride` must
+        // not reach past it
+        let len = self.size() / core::mem::size_of::<T::H>();
+        ensure!(
+            reachable <= len,
+            ErrorKind::InvalidValue;
+            format!("The strides {strides:?} of the shape {shape:?} reach {reachable} elements"),
+            format!("The tensor has {len} elements")
+        );
+        let host = unsafe { Tensor_host(self.tensor) };
+        ensure!(!host.is_null(), ErrorKind::TensorError; "Tensor has no host memory");
+        let data = unsafe { core::slice::from_raw_parts(host.cast::<T::H>(), len) };
+        Ok(TensorView {
+            data,
+            offset: 0,
+            shape,
+            strides,
+            dm_type,
+        })
+    }
+
+    /// Create a strided view of the tensor
+    pub fn view(&self) -> TensorView<'_, T::H> {
+        self.try_view().expect("Failed to create tensor view")
+    }
+}
+
+impl<T: TensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// The length of `axis`
+    ///
+    /// # Panics
+    /// Panics if `axis` is not less than [`Tensor::dimensions`]
+    pub fn length(&self, axis: usize) -> i32 {
+        self.assert_axis(axis);
+        unsafe { Tensor_length(self.tensor, axis as i32) }
+    }
+
+    /// The stride of `axis` in elements
+    ///
+    /// # Panics
+    /// Panics if `axis` is not less than [`Tensor::dimensions`]
+    pub fn stride(&self, axis: usize) -> i32 {
+        self.assert_axis(axis);
+        unsafe { Tensor_stride(self.tensor, axis as i32) }
+    }
+
+    fn assert_axis(&self, axis: usize) {
+        assert!(
+            axis < self.dimensions(),
+            "Axis {axis} is out of bounds for a tensor with {} dimensions",
+            self.dimensions()
+        );
+    }
+}
+
+impl<T: HostTensorType + MutableTensorType> Tensor<T>
+where
+    T::H: HalideType,
+{
+    /// Set the length of `axis`
+    ///
+    /// # Safety
+    /// The tensor doesn't reallocate, the lengths and strides have to stay within the memory of
+    /// the tensor.
+    ///
+    /// # Panics
+    /// Panics if `axis` is not less than [`Tensor::dimensions`]
+    pub unsafe fn set_length(&mut self, axis: usize, length: i32) {
+        self.assert_axis(axis);
+        unsafe { Tensor_setLength(self.tensor, axis as i32, length) }
+    }
+
+    /// Set the stride of `axis` in elements
+    ///
+    /// # Safety
+    /// The tensor doesn't reallocate, the lengths and strides have to stay within the memory of
+    /// the tensor. [`Tensor::view`] rejects strides which reach past the size of the tensor.
+    ///
+    /// # Panics
+    /// Panics if `axis` is not less than [`Tensor::dimensions`]
+    pub unsafe fn set_stride(&mut self, axis: usize, stride: i32) {
+        self.assert_axis(axis);
+        unsafe { Tensor_setStride(self.tensor, axis as i32, stride) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tensor() -> Tensor<Host<f32>> {
+        let mut tensor = Tensor::<Host<f32>>::new([2, 3, 4], DimensionType::NCHW);
+        tensor
+            .host_mut()
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, x)| *x = i as f32);
+        tensor
+    }
+
+    #[test]
+    fn test_view_strides() {
+        let tensor = tensor();
+        let view = tensor.view();
+        assert_eq!(view.shape(), [2, 3, 4]);
+        assert_eq!(view.strides(), [12, 4, 1]);
+        assert!(view.is_contiguous());
+        assert_eq!(view.get(&[1, 2, 3]), Some(&23.0));
+        assert_eq!(view.get(&[2, 0, 0]), None);
+    }
+
+    #[test]
+    fn test_view_select_and_slice() -> Result<()> {
+        let tensor = tensor();
+        let batch = tensor.view().select(0, 1)?;
+        assert_eq!(batch.shape(), [3, 4]);
+        assert_eq!(batch.as_slice(), Some(&tensor.host()[12..]));
+
+        let window = batch.slice(0, 1..)?.slice(1, 1..=2)?;
+        assert_eq!(window.shape(), [2, 2]);
+        assert!(!window.is_contiguous());
+        assert_eq!(window.to_vec(), [17.0, 18.0, 21.0, 22.0]);
+
+        let packed = window.to_contiguous()?;
+        assert_eq!(packed.shape().as_ref(), [2, 2]);
+        assert_eq!(packed.host(), [17.0, 18.0, 21.0, 22.0]);
+
+        assert!(tensor.view().select(3, 0).is_err());
+        assert!(tensor.view().slice(2, 2..5).is_err());
+        Ok(())
+    }
+}
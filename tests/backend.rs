@@ -41,3 +41,41 @@ fn compare_cpu_and_coreml_outputs() {
             assert_eq!(cpu.host(), coreml.host());
         });
 }
+
+#[test]
+fn check_tensor_and_operator_backends_on_cpu() -> Result<()> {
+    let mut net = mnn::Interpreter::from_bytes(Model::new())?;
+    let mut config = ScheduleConfig::new();
+    config.set_type(ForwardType::CPU);
+    let session = net.create_session(config)?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+        assert_eq!(
+            net.tensor_backend(&session, &tensor).unwrap(),
+            ForwardType::CPU
+        );
+    });
+    let mut backends = Vec::new();
+    net.run_session_with_callback(
+        &session,
+        |_, _| true,
+        |_, op| {
+            backends.push(op.backend());
+            true
+        },
+        true,
+    )?;
+    assert!(!backends.is_empty());
+    assert!(
+        backends
+            .iter()
+            .flatten()
+            .all(|backend| *backend == ForwardType::CPU)
+    );
+    for output in net.outputs(&session).iter() {
+        let tensor = output.raw_tensor();
+        assert_eq!(net.raw_tensor_backend(&session, &tensor)?, ForwardType::CPU);
+    }
+    Ok(())
+}
@@ -0,0 +1,124 @@
+pub mod common;
+use common::*;
+
+fn io(net: &Interpreter, session: &Session) -> ((String, usize), (String, usize)) {
+    let inputs = net.inputs(session);
+    let input = inputs.iter().next().expect("No inputs");
+    let outputs = net.outputs(session);
+    let output = outputs.iter().next().expect("No outputs");
+    (
+        (input.name().to_owned(), input.raw_tensor().element_size()),
+        (output.name().to_owned(), output.raw_tensor().element_size()),
+    )
+}
+
+#[test]
+fn test_bind_user_buffers() -> Result<()> {
+    let mut reference = Interpreter::from_bytes(Model::new())?;
+    let reference_session = reference.create_session(ScheduleConfig::new())?;
+    reference.inputs(&reference_session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    reference.run_session(&reference_session)?;
+
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(SessionMode::InputUser);
+    net.set_session_mode(SessionMode::OutputUser);
+    let mut session = net.create_session(ScheduleConfig::new())?;
+    let ((input_name, input_len), (output_name, output_len)) = io(&net, &session);
+    let expected = reference
+        .output::<f32>(&reference_session, &output_name)?
+        .create_host_tensor_from_device(true);
+
+    let input = vec![1.0f32; input_len];
+    let mut output = vec![0.0f32; output_len];
+    let output_ptr = output.as_ptr();
+    let mut bindings = net.bind(&mut session);
+    bindings.bind_input(&input_name, &input)?;
+    bindings.bind_output(&output_name, &mut output)?;
+    bindings.run()?;
+    let tensor = net.raw_output(bindings.session(), &output_name)?;
+    // The output was written to the buffer and not to a copy
+    assert_eq!(
+        unsafe { tensor.unchecked_host_ptr() }.cast_const(),
+        output_ptr.cast()
+    );
+    drop(bindings);
+    assert_eq!(output, expected.host());
+    Ok(())
+}
+
+#[test]
+fn test_bind_requires_user_mode() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let mut session = net.create_session(ScheduleConfig::new())?;
+    let ((input_name, input_len), (output_name, output_len)) = io(&net, &session);
+    let input = vec![1.0f32; input_len];
+    let mut output = vec![0.0f32; output_len];
+    let mut bindings = net.bind(&mut session);
+    let err = bindings.bind_input(&input_name, &input).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NotSupported));
+    let err = bindings.bind_output(&output_name, &mut output).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NotSupported));
+    Ok(())
+}
+
+#[test]
+fn test_bind_uses_modes_of_the_session() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let mut plain = net.create_session(ScheduleConfig::new())?;
+    net.set_session_mode(SessionMode::InputUser);
+    let mut user = net.create_session(ScheduleConfig::new())?;
+    // Modes set after the session was created don't apply to it
+    net.set_session_mode(SessionMode::InputInside);
+    let ((input_name, input_len), _) = io(&net, &plain);
+    let input = vec![1.0f32; input_len];
+    let err = net
+        .bind(&mut plain)
+        .bind_input(&input_name, &input)
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NotSupported));
+    net.bind(&mut user).bind_input(&input_name, &input)?;
+    Ok(())
+}
+
+#[test]
+fn test_session_runs_after_bindings_dropped() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(SessionMode::OutputUser);
+    let mut session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    let (_, (output_name, output_len)) = io(&net, &session);
+    let mut output = vec![0.0f32; output_len];
+    let mut bindings = net.bind(&mut session);
+    bindings.bind_output(&output_name, &mut output)?;
+    bindings.run()?;
+    drop(bindings);
+    // The tensor doesn't point at the dropped buffer anymore
+    let tensor = net.raw_output(&session, &output_name)?;
+    assert_ne!(
+        unsafe { tensor.unchecked_host_ptr() }.cast_const(),
+        output.as_ptr().cast()
+    );
+    drop(output);
+    net.run_session(&session)?;
+    Ok(())
+}
+
+#[test]
+fn test_bind_checks_buffers() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(SessionMode::InputUser);
+    let mut session = net.create_session(ScheduleConfig::new())?;
+    let ((input_name, _), _) = io(&net, &session);
+    let mut bindings = net.bind(&mut session);
+    let err = bindings.bind_input(&input_name, &[1.0f32]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::SizeMismatch { .. }));
+    let err = bindings.bind_input(&input_name, &[1u8]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::HalideTypeMismatch { .. }));
+    Ok(())
+}
@@ -0,0 +1,58 @@
+pub mod common;
+use common::*;
+
+fn setup() -> Result<(Interpreter, Session)> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    Ok((net, session))
+}
+
+#[test]
+fn test_callbacks_borrow_local_state() -> Result<()> {
+    let (mut net, session) = setup()?;
+    let mut before = 0;
+    let mut names = Vec::new();
+    net.run_session_with_callback(
+        &session,
+        |_, _| {
+            before += 1;
+            true
+        },
+        |_, op| {
+            names.push(op.name().to_string_lossy().into_owned());
+            true
+        },
+        true,
+    )?;
+    assert!(before > 0);
+    assert_eq!(before, names.len());
+    Ok(())
+}
+
+#[test]
+fn test_callback_panic_is_resumed() -> Result<()> {
+    let (mut net, session) = setup()?;
+    let mut calls = 0;
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        net.run_session_with_callback(
+            &session,
+            |_, _| true,
+            |_, _| {
+                calls += 1;
+                panic!("callback panicked");
+            },
+            true,
+        )
+    }));
+    let panic = result.unwrap_err();
+    assert_eq!(panic.downcast_ref::<&str>(), Some(&"callback panicked"));
+    // The session is interrupted after the first panic
+    assert_eq!(calls, 1);
+    // The interpreter is still usable afterwards
+    net.run_session(&session)?;
+    Ok(())
+}
@@ -0,0 +1,134 @@
+pub mod common;
+use common::*;
+
+fn setup(mode: SessionMode) -> Result<(Interpreter, Session)> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(mode);
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    Ok((net, session))
+}
+
+#[test]
+fn test_cancelled_run() -> Result<()> {
+    let (mut net, session) = setup(SessionMode::Debug)?;
+    let token = CancellationToken::new();
+    token.clone().cancel();
+    let err = net
+        .run_session_with_cancellation(&session, &token)
+        .unwrap_err();
+    assert!(matches!(
+        err.into_inner().current_context(),
+        ErrorKind::Cancelled
+    ));
+    // The session is still usable after the cancellation
+    token.reset();
+    net.run_session_with_cancellation(&session, &token)?;
+    net.run_session(&session)?;
+    Ok(())
+}
+
+#[test]
+fn test_cancel_from_another_thread() -> Result<()> {
+    let (mut net, session) = setup(SessionMode::Debug)?;
+    let token = CancellationToken::new();
+    let cancel = token.clone();
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    let (cancelled_tx, cancelled_rx) = std::sync::mpsc::channel();
+    let handle = std::thread::spawn(move || {
+        started_rx.recv().unwrap();
+        cancel.cancel();
+        cancelled_tx.send(()).unwrap();
+    });
+    // Block the first operator until the other thread cancelled the token
+    let mut ops = 0;
+    let err = net
+        .run_session_with_callback_and_cancellation(
+            &session,
+            |_, _| true,
+            |_, _| {
+                ops += 1;
+                if ops == 1 {
+                    started_tx.send(()).unwrap();
+                    cancelled_rx.recv().unwrap();
+                }
+                true
+            },
+            &token,
+        )
+        .unwrap_err();
+    handle.join().unwrap();
+    assert!(matches!(
+        err.into_inner().current_context(),
+        ErrorKind::Cancelled
+    ));
+    assert_eq!(ops, 1);
+    Ok(())
+}
+
+#[test]
+fn test_callback_stop_is_not_cancellation() -> Result<()> {
+    let (mut net, session) = setup(SessionMode::Debug)?;
+    let token = CancellationToken::new();
+    let err = net
+        .run_session_with_callback_and_cancellation(&session, |_, _| true, |_, _| false, &token)
+        .unwrap_err();
+    assert!(matches!(
+        err.into_inner().current_context(),
+        ErrorKind::CallbackStopped
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_cancellation_needs_callbacks() -> Result<()> {
+    let (mut net, session) = setup(SessionMode::Release)?;
+    let token = CancellationToken::new();
+    assert!(net.run_session_with_cancellation(&session, &token).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_cancel_from_callback() -> Result<()> {
+    let (mut net, session) = setup(SessionMode::Debug)?;
+    let token = CancellationToken::new();
+    let mut ops = 0;
+    let err = net
+        .run_session_with_callback_and_cancellation(
+            &session,
+            |_, _| true,
+            |_, _| {
+                ops += 1;
+                if ops == 2 {
+                    token.cancel();
+                }
+                true
+            },
+            &token,
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err.into_inner().current_context(),
+        ErrorKind::Cancelled
+    ));
+    // The session stops right after the operator which cancelled it
+    assert_eq!(ops, 2);
+
+    // The same session runs to completion once the token is reset
+    token.reset();
+    let mut ops = 0;
+    net.run_session_with_callback_and_cancellation(
+        &session,
+        |_, _| true,
+        |_, _| {
+            ops += 1;
+            true
+        },
+        &token,
+    )?;
+    assert!(ops > 2);
+    Ok(())
+}
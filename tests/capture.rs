@@ -0,0 +1,107 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_capture_outputs() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    let names = net
+        .outputs(&session)
+        .iter()
+        .map(|x| x.name().to_string())
+        .collect::<Vec<_>>();
+    let captured = net.run_session_capturing::<f32>(&session, &names)?;
+    assert_eq!(captured.len(), names.len());
+    for name in &names {
+        let expected = net
+            .output::<f32>(&session, name)?
+            .create_host_tensor_from_device(true);
+        assert_eq!(captured[name].host(), expected.host());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_capture_missing_tensor() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    assert!(
+        net.run_session_capturing::<f32>(&session, ["does_not_exist"])
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_capture_intermediate_tensor() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    let outputs = net
+        .outputs(&session)
+        .iter()
+        .map(|x| x.name().to_string())
+        .collect::<Vec<_>>();
+    // Every operator except the last ones produces an intermediate tensor
+    let mut ops = Vec::new();
+    net.run_session_with_callback(
+        &session,
+        |_, _| true,
+        |tensors, op| {
+            let name = op.name().to_string_lossy().into_owned();
+            if !tensors.is_empty() && !outputs.contains(&name) {
+                ops.push((name, tensors[0].shape().as_ref().to_vec()));
+            }
+            true
+        },
+        true,
+    )?;
+    drop(session);
+
+    // The converter names operators after their first output, find one that can be saved
+    let (name, shape, session) = ops
+        .into_iter()
+        .find_map(|(name, shape)| {
+            let mut config = ScheduleConfig::new();
+            config.set_save_tensors(&[name.as_str()]).ok()?;
+            let session = net.create_session(config).ok()?;
+            let matches = {
+                let saved = net.raw_output(&session, &name).ok()?;
+                saved.shape().as_ref() == shape && saved.is_type_of::<f32>()
+            };
+            matches.then_some((name, shape, session))
+        })
+        .expect("No intermediate tensor can be saved");
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+
+    let mut expected = None;
+    net.run_session_with_callback(
+        &session,
+        |_, _| true,
+        |tensors, op| {
+            if op.name().to_string_lossy() == name {
+                expected = Some(unsafe {
+                    tensors[0]
+                        .create_host_tensor_from_device(true)
+                        .to_concrete::<Host<f32>>()
+                });
+            }
+            true
+        },
+        true,
+    )?;
+    let expected = expected.expect("The operator wasn't executed");
+
+    let captured = net.run_session_capturing::<f32>(&session, [&name])?;
+    let tensor = &captured[&name];
+    assert_eq!(tensor.shape().as_ref(), shape);
+    assert_eq!(tensor.shape().as_ref(), expected.shape().as_ref());
+    assert_eq!(tensor.host(), expected.host());
+    Ok(())
+}
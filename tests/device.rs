@@ -0,0 +1,66 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_set_cpu_device_ptr() -> Result<()> {
+    let mut tensor = Tensor::<Device<f32>>::new([1, 4], DimensionType::Caffe);
+    let err = unsafe { tensor.set_device_ptr(core::ptr::null(), ForwardType::CPU) }.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidValue));
+
+    let buffer = vec![0.0f32; 4];
+    unsafe { tensor.set_device_ptr(buffer.as_ptr().cast(), ForwardType::CPU)? };
+    let info = tensor.device_info::<CpuDeviceInfo>()?;
+    assert_eq!(info.ptr.cast_const(), buffer.as_ptr().cast());
+    assert_eq!(info.size, core::mem::size_of_val(buffer.as_slice()));
+
+    // The tensor has memory now, it can't be replaced
+    let other = vec![0.0f32; 4];
+    let err =
+        unsafe { tensor.set_device_ptr(other.as_ptr().cast(), ForwardType::CPU) }.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NotSupported));
+    Ok(())
+}
+
+#[test]
+fn test_set_cpu_device_ptr_of_allocated_tensor() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    let inputs = net.inputs(&session);
+    let mut input = inputs.iter().next().expect("No inputs").tensor::<f32>()?;
+    let buffer = vec![0.0f32; input.element_size()];
+    let err =
+        unsafe { input.set_device_ptr(buffer.as_ptr().cast(), ForwardType::CPU) }.unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NotSupported));
+    Ok(())
+}
+
+#[cfg(feature = "opencl")]
+#[repr(C)]
+struct OpenClInfo {
+    _buffer: *mut core::ffi::c_void,
+}
+
+#[cfg(feature = "opencl")]
+unsafe impl DeviceInfo for OpenClInfo {
+    const BACKEND: ForwardType = ForwardType::OpenCL;
+}
+
+#[test]
+fn test_session_device_info() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let mut config = ScheduleConfig::new();
+    config.set_type(ForwardType::CPU);
+    let session = net.create_session(config)?;
+    let outputs = net.outputs(&session);
+    let output = outputs.iter().next().expect("No outputs").tensor::<f32>()?;
+    let info = output.device_info::<CpuDeviceInfo>()?;
+    assert!(!info.ptr.is_null());
+    assert_eq!(info.size, output.size());
+    // The tensor belongs to the CPU backend
+    #[cfg(feature = "opencl")]
+    {
+        let err = output.device_info::<OpenClInfo>().unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotSupported));
+    }
+    Ok(())
+}
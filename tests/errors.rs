@@ -0,0 +1,67 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_invalid_models() {
+    assert!(Interpreter::from_bytes(Vec::<u8>::new()).is_err());
+    assert!(Interpreter::from_bytes(b"definitely not a model").is_err());
+    assert!(Interpreter::from_file("tests/assets/does_not_exist.mnn").is_err());
+    assert!(Interpreter::from_file("tests/errors.rs").is_err());
+}
+
+#[test]
+fn test_invalid_tensor_names() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    assert!(net.input::<f32>(&session, "does_not_exist").is_err());
+    assert!(net.input::<f32>(&session, "nul\0byte").is_err());
+    assert!(net.output::<f32>(&session, "does_not_exist").is_err());
+    assert!(unsafe { net.input_unchecked::<f32>(&session, "does_not_exist") }.is_err());
+    assert!(unsafe { net.input_unchecked::<f32>(&session, "nul\0byte") }.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_mismatched_copy() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    let inputs = net.inputs(&session);
+    let input = inputs.iter().next().expect("No inputs");
+    let mut tensor = input.tensor::<f32>()?;
+    let host = Tensor::<Host<f32>>::try_new([1, 1, 1, 1], DimensionType::Caffe)?;
+    let err = tensor.copy_from_host_tensor(&host).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::SizeMismatch { .. }));
+    assert!(tensor.try_fill(1.0).is_ok());
+    let host = tensor.try_create_host_tensor_from_device(true)?;
+    assert!(host.host().iter().all(|&x| x == 1.0));
+    Ok(())
+}
+
+#[test]
+fn test_wrong_tensor_type() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    let inputs = net.inputs(&session);
+    let input = inputs.iter().next().expect("No inputs");
+    assert!(input.try_name().is_ok());
+    let err = input.tensor::<u8>().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::HalideTypeMismatch { .. }));
+    Ok(())
+}
+
+#[test]
+fn test_invalid_schedule_config() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let config = ScheduleConfig::new().with_num_threads(-1);
+    let err = net.create_session(config).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidValue));
+    let configs = [
+        ScheduleConfig::new(),
+        ScheduleConfig::new().with_num_threads(-1),
+    ];
+    assert!(net.create_multipath_session(configs).is_err());
+    // The interpreter still creates sessions from valid configs, MNN runs 0 threads as 1
+    net.create_session(ScheduleConfig::new().with_num_threads(0))?;
+    net.create_session(ScheduleConfig::new().with_num_threads(1))?;
+    Ok(())
+}
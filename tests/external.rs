@@ -0,0 +1,53 @@
+pub mod common;
+use common::*;
+
+// Generated with `just external-model`
+const EXTERNAL_MODEL: &str = "tests/assets/realesr_external.mnn";
+const EXTERNAL_WEIGHTS: &str = "tests/assets/realesr_external.mnn.weight";
+
+#[test]
+fn test_external_file_not_found() -> Result<()> {
+    let mut net = Interpreter::from_file("tests/assets/realesr.mnn")?;
+    let err = net
+        .set_external_file("tests/assets/does_not_exist.weight")
+        .unwrap_err();
+    assert!(matches!(
+        err.into_inner().current_context(),
+        ErrorKind::IOError
+    ));
+    assert!(
+        Interpreter::from_file_with_external(
+            "tests/assets/realesr.mnn",
+            "tests/assets/does_not_exist.weight"
+        )
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_external_weights() -> Result<()> {
+    let mut net = Interpreter::from_file_with_external(EXTERNAL_MODEL, EXTERNAL_WEIGHTS)?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    net.run_session(&session)?;
+
+    let mut reference = Interpreter::from_bytes(Model::new())?;
+    let reference_session = reference.create_session(ScheduleConfig::new())?;
+    reference.inputs(&reference_session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    reference.run_session(&reference_session)?;
+
+    for output in net.outputs(&session).iter() {
+        let expected = reference.output::<f32>(&reference_session, output.name())?;
+        let output = output.tensor::<f32>()?.create_host_tensor_from_device(true);
+        let expected = expected.create_host_tensor_from_device(true);
+        assert_eq!(output.host(), expected.host());
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+pub mod common;
+use common::*;
+use std::{
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test_run_session_async() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    let name = net
+        .outputs(&session)
+        .iter()
+        .next()
+        .expect("No outputs")
+        .name()
+        .to_owned();
+    net.run_session(&session)?;
+    let expected = net
+        .output::<f32>(&session, &name)?
+        .create_host_tensor_from_device(true);
+
+    block_on(net.run_session_async(&session))?;
+    let output = net
+        .output::<f32>(&session, &name)?
+        .create_host_tensor_from_device(true);
+    assert_eq!(output.host(), expected.host());
+    Ok(())
+}
+
+#[test]
+fn test_drop_pending_future() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    // The session waits for the dropped run before running again
+    drop(net.run_session_async(&session));
+    net.run_session(&session)?;
+    drop(net.run_session_async(&session));
+    drop(session);
+    Ok(())
+}
+
+#[test]
+fn test_outputs_wait_for_pending_run() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    net.run_session(&session)?;
+    let name = net
+        .outputs(&session)
+        .iter()
+        .next()
+        .expect("No outputs")
+        .name()
+        .to_owned();
+    let expected = net
+        .output::<f32>(&session, &name)?
+        .create_host_tensor_from_device(true);
+
+    let pending = net.run_session_async(&session);
+    fn assert_send<T: Send>(_: &T) {}
+    assert_send(&pending);
+    // Reading the outputs blocks until the waiter is done with them
+    let output = net
+        .output::<f32>(&session, &name)?
+        .create_host_tensor_from_device(true);
+    assert_eq!(output.host(), expected.host());
+    block_on(pending)?;
+    Ok(())
+}
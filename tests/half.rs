@@ -0,0 +1,61 @@
+#![cfg(feature = "half")]
+pub mod common;
+use common::*;
+use mnn::half::{bf16, f16};
+
+#[test]
+fn test_half_host_tensors() -> Result<()> {
+    let mut tensor = Tensor::<Host<f16>>::new([1, 3, 2, 2], DimensionType::NCHW);
+    tensor.fill(f16::from_f32(0.5));
+    assert!(tensor.is_type_of::<f16>());
+    assert!(!tensor.is_type_of::<bf16>());
+    assert_eq!(tensor.size(), 12 * 2);
+    assert!(tensor.host().iter().all(|&x| x == f16::from_f32(0.5)));
+    let nhwc = tensor.try_convert_layout(DimensionType::NHWC)?;
+    assert_eq!(nhwc.host(), tensor.host());
+
+    let mut tensor = Tensor::<Host<bf16>>::new([4], DimensionType::NCHW);
+    tensor.fill(bf16::from_f32(2.0));
+    assert!(tensor.is_type_of::<bf16>());
+    assert!(!tensor.is_type_of::<f16>());
+    assert_eq!(tensor.host(), [bf16::from_f32(2.0); 4]);
+    Ok(())
+}
+
+#[test]
+fn test_half_values_through_layouts() -> Result<()> {
+    // 3 channels so NC4HW4 pads them
+    let mut nchw = Tensor::<Host<f16>>::new([1, 3, 2, 2], DimensionType::NCHW);
+    nchw.host_mut()
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = f16::from_f32(i as f32 / 4.0));
+    let nc4hw4 = nchw.try_convert_layout(DimensionType::NC4HW4)?;
+    assert!(nc4hw4.is_type_of::<f16>());
+    assert_eq!(nc4hw4.host_padded().len(), 4 * 4);
+    // Channel 2 of the pixel (1, 0)
+    assert_eq!(nc4hw4.host_padded()[2 * 4 + 2].to_f32(), 10.0 / 4.0);
+    let nhwc = nc4hw4.try_convert_layout(DimensionType::NHWC)?;
+    let view = nhwc.try_view()?;
+    assert_eq!(
+        view.get(&[0, 1, 0, 2]).map(|x| x.to_f32()),
+        Some(10.0 / 4.0)
+    );
+    let back = nhwc.try_convert_layout(DimensionType::NCHW)?;
+    assert_eq!(back.host(), nchw.host());
+    Ok(())
+}
+
+#[test]
+fn test_half_tensor_info_type_check() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let config = ScheduleConfig::new()
+        .with_backend_config(BackendConfig::new().with_precision_mode(PrecisionMode::Low));
+    let session = net.create_session(config)?;
+    let inputs = net.inputs(&session);
+    let input = inputs.iter().next().expect("No inputs");
+    // The inputs of the model are f32 even with a low precision backend
+    let err = input.tensor::<f16>().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::HalideTypeMismatch { .. }));
+    Ok(())
+}
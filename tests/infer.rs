@@ -0,0 +1,90 @@
+pub mod common;
+use common::*;
+
+fn input_info(net: &Interpreter, session: &Session) -> (String, Vec<i32>, usize) {
+    let inputs = net.inputs(session);
+    let input = inputs.iter().next().expect("No inputs");
+    let tensor = input.raw_tensor();
+    (
+        input.name().to_owned(),
+        tensor.shape().to_vec(),
+        tensor.element_size(),
+    )
+}
+
+#[test]
+fn test_infer() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let mut session = net.create_session(ScheduleConfig::new())?;
+    let (name, shape, len) = input_info(&net, &session);
+    // Different values per channel so a packed NC4HW4 copy can't match by accident
+    let data = (0..len).map(|i| (i % 7) as f32 / 7.0).collect::<Vec<_>>();
+    let outputs = net.infer(
+        &mut session,
+        [(name.as_str(), InferInput::with_shape(&data, &shape)?)],
+    )?;
+    assert!(!outputs.is_empty());
+
+    // The same run with MNN converting the layouts
+    let mut host = Tensor::<Host<f32>>::new(&shape, DimensionType::NCHW);
+    host.host_mut().copy_from_slice(&data);
+    net.input::<f32>(&session, &name)?
+        .copy_from_host_tensor(&host)?;
+    net.run_session(&session)?;
+    for output_name in outputs.names() {
+        let output = net.output::<f32>(&session, output_name)?;
+        let dm_type = match output.layout() {
+            DimensionType::NHWC => DimensionType::NHWC,
+            _ => DimensionType::NCHW,
+        };
+        let mut expected = Tensor::<Host<f32>>::new(output.shape(), dm_type);
+        output.copy_to_host_tensor(&mut expected)?;
+        assert_eq!(outputs.get(output_name)?.shape().as_ref(), expected.shape().as_ref());
+        assert_eq!(outputs.slice::<f32>(output_name)?, expected.host());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_infer_checks_inputs() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let mut session = net.create_session(ScheduleConfig::new())?;
+    let (name, _, len) = input_info(&net, &session);
+
+    let short = vec![1.0f32; len - 1];
+    let err = net
+        .infer(&mut session, [(name.as_str(), InferInput::new(&short))])
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::SizeMismatch { .. }));
+
+    let wrong_type = vec![1u8; len];
+    let err = net
+        .infer(
+            &mut session,
+            [(name.as_str(), InferInput::new(&wrong_type))],
+        )
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::HalideTypeMismatch { .. }));
+
+    let data = vec![1.0f32; len];
+    assert!(
+        net.infer(&mut session, [("does_not_exist", InferInput::new(&data))])
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_infer_output_type() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let mut session = net.create_session(ScheduleConfig::new())?;
+    let (name, _, len) = input_info(&net, &session);
+    let data = vec![1.0f32; len];
+    let mut outputs = net.infer(&mut session, [(name, InferInput::new(&data))])?;
+    let output_name = outputs.names().next().expect("No outputs").to_owned();
+    assert!(outputs.slice::<u8>(&output_name).is_err());
+    let tensor = outputs.take::<f32>(&output_name)?;
+    assert_eq!(tensor.element_size(), tensor.host().len());
+    assert!(outputs.get(&output_name).is_err());
+    Ok(())
+}
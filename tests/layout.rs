@@ -0,0 +1,156 @@
+pub mod common;
+use common::*;
+
+/// Skip the channel padding of NC4HW4 data
+fn without_padding<H: Copy>(data: &[H], shape: &[i32]) -> Vec<H> {
+    let channels = shape[1] as usize;
+    let area = shape[2..].iter().product::<i32>() as usize;
+    data.iter()
+        .enumerate()
+        .filter(|(i, _)| (i / (4 * area) % channels.div_ceil(4)) * 4 + i % 4 < channels)
+        .map(|(_, &x)| x)
+        .collect()
+}
+
+#[test]
+fn test_convert_layout_matches_mnn() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    net.run_session(&session)?;
+    let outputs = net.outputs(&session);
+    let output = outputs.iter().next().expect("No outputs").tensor::<f32>()?;
+    let shape = output.shape();
+    assert_eq!(output.get_dimension_type(), DimensionType::NCHW);
+
+    let mut nchw = Tensor::<Host<f32>>::new(shape, DimensionType::NCHW);
+    nchw.host_mut()
+        .copy_from_slice(&output.try_map(DimensionType::NCHW)?);
+    assert_eq!(nchw.layout(), DimensionType::NCHW);
+
+    let nhwc = nchw.try_convert_layout(DimensionType::NHWC)?;
+    assert_eq!(nhwc.layout(), DimensionType::NHWC);
+    assert_eq!(nhwc.host(), &*output.try_map(DimensionType::NHWC)?);
+
+    let nc4hw4 = nhwc.try_convert_layout(DimensionType::NC4HW4)?;
+    assert_eq!(nc4hw4.layout(), DimensionType::NC4HW4);
+    assert_eq!(
+        without_padding(nc4hw4.host_padded(), shape.as_ref()),
+        without_padding(&output.try_map(DimensionType::NC4HW4)?, shape.as_ref())
+    );
+
+    let back = nc4hw4.try_convert_layout(DimensionType::NCHW)?;
+    assert_eq!(back.host(), nchw.host());
+    Ok(())
+}
+
+fn to_ffi(layout: DimensionType) -> ffi::DimensionType {
+    match layout {
+        DimensionType::TensorFlow => ffi::DimensionType::TENSORFLOW,
+        DimensionType::Caffe => ffi::DimensionType::CAFFE,
+        DimensionType::CaffeC4 => ffi::DimensionType::CAFFE_C4,
+    }
+}
+
+/// Convert `data` with `shape` in `from` to `to` with the CPU converter of MNN
+///
+/// Returns `None` for the types MNN can't convert.
+fn mnn_convert<H: HalideType + Copy>(
+    data: &[H],
+    shape: &[i32],
+    from: DimensionType,
+    to: DimensionType,
+) -> Option<Vec<H>> {
+    let mut data = data.to_vec();
+    unsafe {
+        let src = ffi::Tensor_createWith(
+            shape.as_ptr(),
+            shape.len(),
+            H::halide_type_of(),
+            data.as_mut_ptr().cast(),
+            to_ffi(from),
+        );
+        assert!(!src.is_null());
+        let dst = ffi::Tensor_createFromTensor(src, to_ffi(to), 1);
+        assert!(!dst.is_null());
+        let converted = ffi::Tensor_convertLayout(src, dst) == 1;
+        ffi::Tensor_destroy(src);
+        let dst = Tensor::<Host<H>>::from_ptr(dst);
+        converted.then(|| elements(&dst))
+    }
+}
+
+/// The elements of a host tensor without the channel padding of NC4HW4
+fn elements<H: HalideType + Copy>(tensor: &Tensor<Host<H>>) -> Vec<H> {
+    match tensor.layout() {
+        DimensionType::CaffeC4 => without_padding(tensor.host_padded(), tensor.shape().as_ref()),
+        _ => tensor.host().to_vec(),
+    }
+}
+
+fn roundtrip<H: HalideType + Copy + PartialEq + core::fmt::Debug>(
+    value: impl Fn(usize) -> H,
+) -> Result<()> {
+    // 5 channels so NC4HW4 needs padding
+    let mut nchw = Tensor::<Host<H>>::new([2, 5, 3, 2], DimensionType::NCHW);
+    nchw.host_mut()
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = value(i));
+    let nhwc = nchw.try_convert_layout(DimensionType::NHWC)?;
+    assert_eq!(nhwc.shape().as_ref(), [2, 3, 2, 5]);
+    // Channel 1 of the pixel (1, 0) of the second batch item
+    assert_eq!(
+        nhwc.host()[((3 + 1) * 2) * 5 + 1],
+        value(((5 + 1) * 3 + 1) * 2)
+    );
+    let nc4hw4 = nhwc.try_convert_layout(DimensionType::NC4HW4)?;
+    assert_eq!(nc4hw4.shape().as_ref(), [2, 5, 3, 2]);
+    assert_eq!(nc4hw4.host().len(), 2 * 5 * 3 * 2);
+    assert_eq!(nc4hw4.host_padded().len(), 2 * 8 * 3 * 2);
+    let back = nc4hw4.try_convert_layout(DimensionType::NCHW)?;
+    assert_eq!(back.host(), nchw.host());
+    assert_eq!(
+        nc4hw4.try_convert_layout(DimensionType::NHWC)?.host(),
+        nhwc.host()
+    );
+
+    // Every conversion gives the same elements as the converter of MNN
+    for (src, dst) in [(&nchw, &nhwc), (&nhwc, &nc4hw4), (&nc4hw4, &back)] {
+        let (from, to) = (src.layout(), dst.layout());
+        let shape = src.shape();
+        let expected = mnn_convert(src.host_padded(), shape.as_ref(), from, to);
+        // MNN has no 64 bit tensors, it loads them as 32 bit
+        if core::mem::size_of::<H>() == 8 {
+            assert!(expected.is_none());
+            continue;
+        }
+        let expected = expected.expect("MNN failed to convert the tensor");
+        assert_eq!(elements(dst), expected, "{from:?} to {to:?}");
+    }
+    Ok(())
+}
+
+#[test]
+fn test_convert_layout_all_types() -> Result<()> {
+    roundtrip(|i| i as f32)?;
+    roundtrip(|i| i as f64)?;
+    roundtrip(|i| i % 2 == 0)?;
+    roundtrip(|i| i as u8)?;
+    roundtrip(|i| i as u16)?;
+    roundtrip(|i| i as u32)?;
+    roundtrip(|i| i as u64)?;
+    roundtrip(|i| i as i8)?;
+    roundtrip(|i| i as i16)?;
+    roundtrip(|i| i as i32)?;
+    roundtrip(|i| i as i64)?;
+    #[cfg(feature = "half")]
+    {
+        roundtrip(|i| mnn::half::f16::from_f32(i as f32))?;
+        roundtrip(|i| mnn::half::bf16::from_f32(i as f32))?;
+    }
+    Ok(())
+}
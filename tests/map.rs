@@ -0,0 +1,80 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_map_tensors() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    for input in net.inputs(&session).iter() {
+        let mut tensor = input.tensor::<f32>()?;
+        let dm_type = tensor.get_dimension_type();
+        tensor.try_map_mut(dm_type)?.fill(1.0);
+        assert!(tensor.try_map(dm_type)?.iter().all(|&x| x == 1.0));
+    }
+    net.run_session(&session)?;
+    for output in net.outputs(&session).iter() {
+        let tensor = output.tensor::<f32>()?;
+        let expected = tensor.create_host_tensor_from_device(true);
+        let mapped = tensor.try_map(tensor.get_dimension_type())?;
+        assert_eq!(&*mapped, expected.host());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_map_other_dimension_type() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    let inputs = net.inputs(&session);
+    let input = inputs.iter().next().expect("No inputs");
+    let mut tensor = input.tensor::<f32>()?;
+    let (batch, channels) = (tensor.batch() as usize, tensor.channel() as usize);
+    let area = (tensor.height() * tensor.width()) as usize;
+    // Map in the layout the tensor isn't stored in so MNN has to convert
+    let (write, read) = match tensor.layout() {
+        DimensionType::NHWC => (DimensionType::NCHW, DimensionType::NHWC),
+        _ => (DimensionType::NHWC, DimensionType::NCHW),
+    };
+    let nhwc = |n: usize, c: usize, s: usize| (n * area + s) * channels + c;
+    let nchw = |n: usize, c: usize, s: usize| (n * channels + c) * area + s;
+    let offset = |layout: DimensionType, n, c, s| match layout {
+        DimensionType::NHWC => nhwc(n, c, s),
+        _ => nchw(n, c, s),
+    };
+    {
+        let mut mapped = tensor.try_map_mut(write)?;
+        assert_eq!(mapped.len(), batch * channels * area);
+        mapped
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, x)| *x = i as f32);
+    }
+    let mapped = tensor.try_map(read)?;
+    assert_eq!(mapped.dimension_type(), read);
+    for n in 0..batch {
+        for c in 0..channels {
+            for s in 0..area {
+                assert_eq!(
+                    mapped[offset(read, n, c, s)],
+                    offset(write, n, c, s) as f32
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_map_nc4hw4_needs_nc4hw4_tensor() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    for input in net.inputs(&session).iter() {
+        let tensor = input.tensor::<f32>()?;
+        if tensor.layout() == DimensionType::NC4HW4 {
+            continue;
+        }
+        let err = tensor.try_map(DimensionType::NC4HW4).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::NotSupported));
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_model_info_matches_session() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let info = net.model_info()?;
+    assert!(!info.inputs().is_empty());
+    assert!(!info.outputs().is_empty());
+    assert!(!info.version().is_empty());
+
+    let session = net.create_session(ScheduleConfig::new())?;
+    let inputs = net.inputs(&session);
+    assert_eq!(inputs.size(), info.inputs().len());
+    for tensor_info in inputs.iter() {
+        let descriptor = info
+            .input(tensor_info.name())
+            .expect("Input missing from model info");
+        assert!(descriptor.is_type_of::<f32>());
+        if !descriptor.is_dynamic() {
+            let tensor = tensor_info.raw_tensor();
+            assert_eq!(descriptor.shape(), Some(tensor.shape().as_ref()));
+        }
+    }
+    let outputs = net.outputs(&session);
+    for tensor_info in outputs.iter() {
+        let descriptor = info
+            .output(tensor_info.name())
+            .expect("Output missing from model info");
+        // Whatever the model declares about an output has to match the session
+        let tensor = tensor_info.raw_tensor();
+        if let Some(halide_type) = descriptor.halide_type() {
+            assert_eq!(halide_type.code, tensor.get_type().code);
+            assert_eq!(halide_type.bits, tensor.get_type().bits);
+        }
+        if !descriptor.is_dynamic() {
+            assert_eq!(descriptor.shape(), Some(tensor.shape().as_ref()));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_model_info_dynamic_inputs() -> Result<()> {
+    let net = Interpreter::from_file("tests/assets/resizing.mnn")?;
+    let info = net.model_info()?;
+    for name in ["mask", "original", "inpainted"] {
+        let descriptor = info.input(name).expect("Input missing from model info");
+        assert!(descriptor.is_dynamic());
+        assert!(descriptor.shape().is_none() || !descriptor.dynamic_axes().is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_model_metadata() -> Result<()> {
+    let net = Interpreter::from_bytes(Model::new())?;
+    let version = net.model_version()?;
+    assert!(!version.is_empty());
+    let info = net.model_info()?;
+    assert_eq!(info.version(), version);
+    assert_eq!(info.biz_code(), net.biz_code());
+    assert_eq!(info.uuid(), net.uuid());
+    Ok(())
+}
@@ -0,0 +1,53 @@
+pub mod common;
+use common::*;
+use std::time::Duration;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_session_pool() -> Result<()> {
+    assert_send_sync::<SessionPool>();
+    let net = Interpreter::from_bytes(Model::new())?;
+    let pool = SessionPool::new(net, ScheduleConfig::new(), 2)?;
+    assert_eq!(pool.size(), 2);
+    std::thread::scope(|s| {
+        let handles = (0..4)
+            .map(|_| {
+                s.spawn(|| -> Result<()> {
+                    let session = pool.get();
+                    session.inputs().iter().for_each(|x| {
+                        let mut tensor = x.tensor::<f32>().expect("No tensor");
+                        tensor.fill(1.0f32);
+                    });
+                    session.run()?;
+                    Ok(())
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().expect("Worker panicked"))
+    })?;
+    assert_eq!(pool.available(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_session_pool_exhausted() -> Result<()> {
+    let net = Interpreter::from_bytes(Model::new())?;
+    let pool = SessionPool::new(net, ScheduleConfig::new(), 1)?;
+    let session = pool.get();
+    assert!(pool.try_get().is_none());
+    assert!(pool.get_timeout(Duration::from_millis(10)).is_none());
+    drop(session);
+    assert!(pool.try_get().is_some());
+    assert_eq!(pool.available(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_empty_session_pool() -> Result<()> {
+    let net = Interpreter::from_bytes(Model::new())?;
+    assert!(SessionPool::new(net, ScheduleConfig::new(), 0).is_err());
+    Ok(())
+}
@@ -0,0 +1,36 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_profiler() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(SessionMode::Debug);
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    let report = Profiler::new()
+        .with_warmup(1)
+        .with_runs(3)
+        .profile(&mut net, &session)?;
+    assert_eq!(report.runs(), 3);
+    assert!(!report.ops().is_empty());
+    assert!(report.ops().iter().all(|op| op.calls == 3));
+    let summaries = report.by_op_type();
+    let total: usize = summaries.iter().map(|summary| summary.ops).sum();
+    assert_eq!(total, report.ops().len());
+    #[cfg(feature = "serde")]
+    assert!(report.to_chrome_trace().starts_with("{\"traceEvents\":["));
+    Ok(())
+}
+
+#[test]
+fn test_profiler_rejects_release_mode() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(SessionMode::Release);
+    let session = net.create_session(ScheduleConfig::new())?;
+    let err = Profiler::new().profile(&mut net, &session).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InterpreterError));
+    Ok(())
+}
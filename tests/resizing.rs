@@ -14,15 +14,15 @@ pub fn test_resizing() -> Result<()> {
 
     let now = std::time::Instant::now();
     let mut mask = unsafe { net.input_unresized::<f32>(&session, "mask") }?;
-    net.resize_tensor(&mut mask, [2048, 2048]);
+    net.resize_tensor(&mut mask, [2048, 2048])?;
     drop(mask);
 
     let mut og = unsafe { net.input_unresized::<f32>(&session, "original") }?;
-    net.resize_tensor(&mut og, [2048, 2048, 3]);
+    net.resize_tensor(&mut og, [2048, 2048, 3])?;
     drop(og);
 
     let mut pain = unsafe { net.input_unresized::<f32>(&session, "inpainted") }?;
-    net.resize_tensor(&mut pain, [2048, 2048, 3]);
+    net.resize_tensor(&mut pain, [2048, 2048, 3])?;
     drop(pain);
 
     net.resize_session(&mut session);
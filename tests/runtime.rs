@@ -0,0 +1,143 @@
+pub mod common;
+use common::*;
+use std::sync::Arc;
+
+fn run(net: &mut Interpreter, session: &Session) -> Result<()> {
+    net.inputs(session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    net.run_session(session)?;
+    Ok(())
+}
+
+#[test]
+fn test_shared_runtime() -> Result<()> {
+    let runtime = Arc::new(Runtime::new(ScheduleConfig::new())?);
+    let mut net1 = Interpreter::from_bytes(Model::new())?;
+    let mut net2 = Interpreter::from_bytes(Model::new())?;
+    let session1 = net1.create_session_with_runtime(ScheduleConfig::new(), &runtime)?;
+    let session2 = net2.create_session_with_runtime(ScheduleConfig::new(), &runtime)?;
+    let session3 = net1.create_session_with_runtime(ScheduleConfig::new(), &runtime)?;
+    assert_eq!(Arc::strong_count(&runtime), 4);
+    run(&mut net1, &session1)?;
+    run(&mut net2, &session2)?;
+    run(&mut net1, &session3)?;
+    Ok(())
+}
+
+#[test]
+fn test_shared_runtime_across_threads() -> Result<()> {
+    let runtime = Arc::new(Runtime::new(ScheduleConfig::new())?);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let runtime = Arc::clone(&runtime);
+                scope.spawn(move || -> Result<()> {
+                    let mut net = Interpreter::from_bytes(Model::new())?;
+                    let session =
+                        net.create_session_with_runtime(ScheduleConfig::new(), &runtime)?;
+                    run(&mut net, &session)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().expect("Thread panicked"))
+    })?;
+    assert_eq!(Arc::strong_count(&runtime), 1);
+    Ok(())
+}
+
+#[test]
+fn test_runtime_rejects_invalid_config() {
+    let mut config = ScheduleConfig::new();
+    config.set_num_threads(-1);
+    assert!(Runtime::from_configs([config.clone()]).is_err());
+    let runtime = Arc::new(Runtime::new(ScheduleConfig::new()).expect("Failed to create runtime"));
+    let mut net = Interpreter::from_bytes(Model::new()).expect("Failed to load model");
+    assert!(
+        net.create_multipath_session_with_runtime([config], &runtime)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_session_keeps_runtime_alive() -> Result<()> {
+    let runtime = Arc::new(Runtime::new(ScheduleConfig::new())?);
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session_with_runtime(ScheduleConfig::new(), &runtime)?;
+    drop(runtime);
+    run(&mut net, &session)?;
+    Ok(())
+}
+
+#[test]
+fn test_multipath_session_with_runtime() -> Result<()> {
+    let runtime = Arc::new(Runtime::from_configs([ScheduleConfig::new()])?);
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_multipath_session_with_runtime([ScheduleConfig::new()], &runtime)?;
+    run(&mut net, &session)?;
+    Ok(())
+}
+
+#[test]
+fn test_runtime_without_configs() {
+    assert!(Runtime::from_configs(Vec::new()).is_err());
+}
+
+#[test]
+fn test_runtime_cache() -> Result<()> {
+    let mut config = ScheduleConfig::new();
+    config.set_type(ForwardType::CPU);
+    let runtime = Arc::new(Runtime::new(config.clone())?);
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session_with_runtime(config.clone(), &runtime)?;
+    run(&mut net, &session)?;
+    assert!(runtime.has_backend(ForwardType::CPU));
+    let key = CacheKey::new("realesr", ForwardType::CPU);
+    // The cpu backend doesn't have a tuning cache
+    assert!(runtime.cache(&key)?.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_runtime_rejects_invalid_cache() -> Result<()> {
+    let mut config = ScheduleConfig::new();
+    config.set_type(ForwardType::CPU);
+    let mut runtime = Runtime::new(config)?;
+    let blob = {
+        // A valid header without the key and the cache
+        let mut blob = b"MNNC".to_vec();
+        blob.extend_from_slice(&1u32.to_le_bytes());
+        blob
+    };
+    let key = CacheKey::new("realesr", ForwardType::CPU);
+    let err = runtime.set_cache(&key, blob).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidCache));
+    let err = runtime.set_cache(&key, Vec::new()).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidCache));
+    Ok(())
+}
+
+#[test]
+fn test_interpreter_session_cache() -> Result<()> {
+    let mut config = ScheduleConfig::new();
+    config.set_type(ForwardType::CPU);
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let key = CacheKey::new("realesr", ForwardType::CPU);
+    let session = net.create_session_with_cache(config.clone(), &key, None)?;
+    run(&mut net, &session)?;
+    // The cpu backend doesn't have a tuning cache
+    assert!(net.session_cache(&session, &key)?.is_none());
+    // Invalid blobs are rejected before the session is created
+    let err = net
+        .create_session_with_cache(config.clone(), &key, Some(b"MNNC".to_vec()))
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidCache));
+    // Sessions without a runtime don't have access to the cache
+    let session = net.create_session(config)?;
+    let err = net.session_cache(&session, &key).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidCache));
+    Ok(())
+}
@@ -0,0 +1,71 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_session_hints() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_hint(SessionHint::WinogradMemoryLevel(0))?;
+    net.set_session_hint(SessionHint::CpuLittleCoreDecreaseRate(30))?;
+    net.set_session_hint(SessionHint::GeometryComputeMask(GeometryComputeMask::ALL))?;
+    assert!(
+        net.set_session_hint(SessionHint::WinogradMemoryLevel(8))
+            .is_err()
+    );
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    net.run_session(&session)?;
+    // Only applies to the sessions created afterwards
+    net.set_session_hint(SessionHint::WinogradMemoryLevel(1))?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.run_session(&session)?;
+    Ok(())
+}
+
+#[test]
+fn test_resize_mode_rejects_geometry_mask_without_cache() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(SessionMode::ResizeCheck);
+    let err = net
+        .set_session_hint(SessionHint::GeometryComputeMask(
+            GeometryComputeMask::FUSE_REGION,
+        ))
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidSessionHint));
+    net.set_session_hint(SessionHint::GeometryComputeMask(
+        GeometryComputeMask::FUSE_REGION | GeometryComputeMask::OPEN_CACHE,
+    ))?;
+    net.create_session(ScheduleConfig::new())?;
+    Ok(())
+}
+
+#[test]
+fn test_geometry_mask_without_cache_rejects_resize_mode() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_hint(SessionHint::GeometryComputeMask(GeometryComputeMask::NONE))?;
+    net.set_session_mode(SessionMode::ResizeFix);
+    let err = net.create_session(ScheduleConfig::new()).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidSessionHint));
+    net.set_session_hint(SessionHint::GeometryComputeMask(GeometryComputeMask::ALL))?;
+    net.create_session(ScheduleConfig::new())?;
+    Ok(())
+}
+
+#[test]
+fn test_session_modes_override_their_group() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    net.set_session_mode(SessionMode::ResizeCheck);
+    net.set_session_mode(SessionMode::ResizeFix);
+    net.set_session_hint(SessionHint::GeometryComputeMask(
+        GeometryComputeMask::FUSE_REGION | GeometryComputeMask::OPEN_CACHE,
+    ))?;
+    net.set_session_mode(SessionMode::Release);
+    net.set_session_mode(SessionMode::Debug);
+    let session = net.create_session(ScheduleConfig::new())?;
+    // Debug was set after Release, so callbacks are allowed
+    let token = CancellationToken::new();
+    net.run_session_with_cancellation(&session, &token)?;
+    Ok(())
+}
@@ -0,0 +1,34 @@
+pub mod common;
+use common::*;
+
+#[test]
+fn test_session_info() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let mut config = ScheduleConfig::new();
+    config.set_type(ForwardType::CPU);
+    config.set_num_threads(2);
+    let session = net.create_session(config)?;
+    let info = net.session_info(&session)?;
+    assert_eq!(info.requested, [ForwardType::CPU]);
+    assert_eq!(info.backends.first(), Some(&ForwardType::CPU));
+    assert!(info.thread_number > 0);
+    assert_eq!(info.memory, net.memory(&session)?);
+    assert_eq!(info.flops, net.flops(&session)?);
+    assert!(info.flops > 0.0);
+    Ok(())
+}
+
+#[test]
+fn test_session_info_with_runtime() -> Result<()> {
+    let mut cpu = ScheduleConfig::new();
+    cpu.set_type(ForwardType::CPU);
+    // The runtime can have more backends than the session requested
+    let runtime = std::sync::Arc::new(Runtime::from_configs([cpu.clone(), ScheduleConfig::new()])?);
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session_with_runtime(cpu, &runtime)?;
+    let info = net.session_info(&session)?;
+    assert_eq!(info.backends, net.backends(&session)?);
+    assert!(info.backends.contains(&ForwardType::CPU));
+    assert!(info.unknown_backends.is_empty());
+    Ok(())
+}
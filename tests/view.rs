@@ -0,0 +1,110 @@
+pub mod common;
+use common::*;
+
+fn iota(shape: [i32; 4], dm_type: DimensionType) -> Tensor<Host<f32>> {
+    let mut tensor = Tensor::<Host<f32>>::new(shape, dm_type);
+    tensor
+        .host_mut()
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = i as f32);
+    tensor
+}
+
+#[test]
+fn test_view_session_output() -> Result<()> {
+    let mut net = Interpreter::from_bytes(Model::new())?;
+    let session = net.create_session(ScheduleConfig::new())?;
+    net.inputs(&session).iter().for_each(|x| {
+        let mut tensor = x.tensor::<f32>().expect("No tensor");
+        tensor.fill(1.0f32);
+    });
+    net.run_session(&session)?;
+    let outputs = net.outputs(&session);
+    let output = outputs.iter().next().expect("No outputs").tensor::<f32>()?;
+
+    let packed = output.create_host_tensor_from_device(true);
+    if packed.layout() == DimensionType::NC4HW4 {
+        assert!(packed.try_view().is_err());
+    }
+
+    let mut host = Tensor::<Host<f32>>::new(output.shape(), DimensionType::NCHW);
+    output.copy_to_host_tensor(&mut host)?;
+    let view = host.try_view()?;
+    let shape = host.shape();
+    assert_eq!(
+        view.shape(),
+        shape
+            .as_ref()
+            .iter()
+            .map(|&dim| dim as usize)
+            .collect::<Vec<_>>()
+    );
+    assert!(view.is_contiguous());
+    assert_eq!(view.to_vec(), host.host());
+
+    // The first channel of the first image
+    let plane = view.select(0, 0)?.select(0, 0)?;
+    let area = plane.len();
+    assert_eq!(plane.as_slice(), Some(&host.host()[..area]));
+    Ok(())
+}
+
+#[test]
+fn test_view_nhwc() -> Result<()> {
+    let tensor = iota([1, 2, 3, 4], DimensionType::NHWC);
+    let view = tensor.try_view()?;
+    assert_eq!(view.shape(), [1, 2, 3, 4]);
+    assert_eq!(view.strides(), [24, 12, 4, 1]);
+
+    // A single channel of an NHWC image isn't contiguous
+    let channel = view.select(0, 0)?.select(2, 1)?;
+    assert_eq!(channel.shape(), [2, 3]);
+    assert!(!channel.is_contiguous());
+    assert_eq!(channel.to_vec(), [1.0, 5.0, 9.0, 13.0, 17.0, 21.0]);
+    let packed = channel.to_contiguous()?;
+    assert_eq!(packed.host(), [1.0, 5.0, 9.0, 13.0, 17.0, 21.0]);
+    Ok(())
+}
+
+#[test]
+fn test_view_custom_strides() -> Result<()> {
+    let mut tensor = iota([1, 2, 3, 3], DimensionType::NCHW);
+    // Transpose the rows and columns, the memory of the tensor isn't changed
+    unsafe {
+        tensor.set_stride(2, 1);
+        tensor.set_stride(3, 3);
+    }
+    assert_eq!(tensor.length(3), 3);
+    assert_eq!(tensor.stride(3), 3);
+    let view = tensor.try_view()?;
+    assert_eq!(view.shape(), [1, 2, 3, 3]);
+    assert_eq!(view.strides(), [18, 9, 1, 3]);
+    assert!(!view.is_contiguous());
+    assert_eq!(view.get(&[0, 1, 0, 2]), Some(&15.0));
+    assert_eq!(
+        view.select(0, 0)?.select(0, 0)?.to_vec(),
+        [0.0, 3.0, 6.0, 1.0, 4.0, 7.0, 2.0, 5.0, 8.0]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_view_rejects_strides_past_the_tensor() {
+    let mut tensor = iota([1, 2, 3, 4], DimensionType::NCHW);
+    // Every other column of the first two rows reaches past the size of the cropped tensor
+    unsafe {
+        tensor.set_length(2, 2);
+        tensor.set_length(3, 2);
+        tensor.set_stride(3, 2);
+    }
+    let err = tensor.try_view().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidValue));
+}
+
+#[test]
+#[should_panic]
+fn test_length_of_missing_axis() {
+    let tensor = iota([1, 2, 3, 4], DimensionType::NCHW);
+    tensor.length(4);
+}
@@ -6,6 +6,7 @@ use std::{
     collections::BTreeMap,
     io::IsTerminal,
     path::{Path, PathBuf},
+    sync::Arc,
     time::Duration,
 };
 use thiserror::Error;
@@ -99,7 +100,8 @@ pub struct ConfigData {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, ValueEnum)]
 pub enum DataType {
-    // Float16,
+    Float16,
+    BFloat16,
     Float32,
     Int32,
     Int64,
@@ -110,6 +112,8 @@ pub enum DataType {
 impl DataType {
     pub fn mas(&self, lhs: &[u8], rhs: &[u8]) -> f64 {
         match self {
+            Self::Float16 => Self::mean_absolute_error_bytes::<half::f16>(lhs, rhs),
+            Self::BFloat16 => Self::mean_absolute_error_bytes::<half::bf16>(lhs, rhs),
             Self::Float32 => Self::mean_absolute_error_bytes::<f32>(lhs, rhs),
             Self::Int32 => Self::mean_absolute_error_bytes::<i32>(lhs, rhs),
             Self::Int64 => Self::mean_absolute_error_bytes::<i64>(lhs, rhs),
@@ -553,6 +557,8 @@ pub fn bench_all(
 }
 
 // #[tracing::instrument(skip(model))]
+// The runtime is only used from this thread
+#[allow(clippy::arc_with_non_send_sync)]
 pub fn bench(
     w: u8,
     sc: ScheduleConfig,
@@ -566,26 +572,49 @@ pub fn bench(
 
     tracing::info!("Benching {:?}", sc);
     let mut net = mnn::Interpreter::from_file(&model).cc(BenchError)?;
+    let runtime = Arc::new(mnn::Runtime::new(sc.clone()).cc(BenchError)?);
 
     bar.set_message("Creating session without cache");
     not_terminal.then(|| eprintln!("Creating session without cache"));
-    let (mut uncached, initial_load_time) = timeit(|| {
+    let (uncached, initial_load_time) = timeit(|| {
         tracing::trace!("Creating session without cache");
-        net.create_session(sc.clone())
+        net.create_session_with_runtime(sc.clone(), &runtime)
     })
     .cc(BenchError)?;
-    let temp_file = temp_file_path()?;
-    net.set_cache_file(&temp_file, 128).cc(BenchError)?;
-    net.update_cache_file(&mut uncached).cc(BenchError)?;
+    // The runtime stores the cache under the backend MNN picked for Auto
+    let backend = match sc.get_type() {
+        mnn::ForwardType::Auto => net
+            .backends(&uncached)
+            .cc(BenchError)?
+            .first()
+            .copied()
+            .unwrap_or(mnn::ForwardType::Auto),
+        forward_type => forward_type,
+    };
+    let key = mnn::CacheKey::new(model.as_ref().to_string_lossy(), backend);
+    let cache = if runtime.has_backend(key.backend()) {
+        runtime.cache(&key).cc(BenchError)?
+    } else {
+        tracing::warn!(
+            "The runtime has no {} backend, the session with cache is created without one",
+            key.backend().to_str()
+        );
+        None
+    };
     drop(uncached);
+    drop(runtime);
     drop(net);
     let mut net = mnn::Interpreter::from_file(&model).cc(BenchError)?;
-    net.set_cache_file(&temp_file, 128).cc(BenchError)?;
+    let mut runtime = mnn::Runtime::new(sc.clone()).cc(BenchError)?;
+    if let Some(cache) = cache {
+        runtime.set_cache(&key, cache).cc(BenchError)?;
+    }
+    let runtime = Arc::new(runtime);
     bar.set_message("Creating session with cache");
     not_terminal.then(|| eprintln!("Creating session with cache"));
     let (session, cached_load_time) = timeit(|| {
-        tracing::trace!("Creating session with cache {temp_file:?}");
-        net.create_session(sc.clone())
+        tracing::trace!("Creating session with cache");
+        net.create_session_with_runtime(sc.clone(), &runtime)
     })
     .cc(BenchError)?;
     for c in 0..w {
@@ -650,7 +679,6 @@ pub fn bench(
     }
     let memory = net.memory(&session).cc(BenchError)?;
     let flops = net.flops(&session).cc(BenchError)?;
-    temp_file.close().cc(BenchError)?;
     Ok(Metric {
         schedule_config: sc,
         memory,
@@ -669,12 +697,6 @@ pub fn timeit<F: FnOnce() -> Result<T, E>, T, E>(f: F) -> Result<(T, Duration),
     Ok((result, duration))
 }
 
-pub fn temp_file_path() -> Result<tempfile::TempPath> {
-    Ok(tempfile::NamedTempFile::new()
-        .cc(BenchError)?
-        .into_temp_path())
-}
-
 pub fn p_read(reader: impl std::io::Read + Send + Sync + 'static) -> ProgressBar {
     let bar = ProgressBar::new_spinner().with_style(
         indicatif::ProgressStyle::default_bar()